@@ -2,11 +2,13 @@
 // Copyright (c) 2023-2025 Rust Nostr Developers
 // Distributed under the MIT software license
 
+use std::collections::HashMap;
 use std::fmt;
 use std::ops::Deref;
 use std::sync::Arc;
 
 use nostr_sdk::authenticator::{self, Authenticator as _};
+use tokio::sync::Mutex;
 use uniffi::Object;
 
 use crate::error::{NostrSdkError, Result};
@@ -25,8 +27,42 @@ pub trait Authenticator: Send + Sync {
     ) -> Result<Option<Arc<Event>>>;
 }
 
+/// IDs of the last authentication events made by the client authenticator, by relay
+///
+/// Used to match the `OK` messages of the relays against the `AUTH` events actually sent.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct SentAuthEvents {
+    ids: Arc<Mutex<HashMap<nostr::RelayUrl, nostr::EventId>>>,
+}
+
+impl SentAuthEvents {
+    async fn insert(&self, relay_url: nostr::RelayUrl, event_id: nostr::EventId) {
+        let mut ids = self.ids.lock().await;
+        ids.insert(relay_url, event_id);
+    }
+
+    /// Remove the authentication event of the relay if its ID matches
+    ///
+    /// Returns `true` if the `event_id` is the one of the last `AUTH` event sent to the relay.
+    pub(crate) async fn take(
+        &self,
+        relay_url: &nostr::RelayUrl,
+        event_id: &nostr::EventId,
+    ) -> bool {
+        let mut ids = self.ids.lock().await;
+
+        if ids.get(relay_url) != Some(event_id) {
+            return false;
+        }
+
+        ids.remove(relay_url);
+        true
+    }
+}
+
 pub(crate) struct FFI2RustAuthenticator {
     pub(crate) inner: Arc<dyn Authenticator>,
+    pub(crate) sent: SentAuthEvents,
 }
 
 impl fmt::Debug for FFI2RustAuthenticator {
@@ -100,7 +136,11 @@ mod inner {
                     .map_err(Error::other)?;
 
                 match event {
-                    Some(event) => Ok(event.as_ref().deref().clone()),
+                    Some(event) => {
+                        let event: Event = event.as_ref().deref().clone();
+                        self.sent.insert(relay_url.clone(), event.id).await;
+                        Ok(event)
+                    }
                     None => Err(Error::other(MiddleError::new(
                         "Received a null authentication event.",
                     ))),
@@ -0,0 +1,175 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+use nostr::nips::nip65;
+use nostr_sdk::{client, relay};
+
+use super::send_event::SendEventTarget;
+use crate::client::Client;
+use crate::client::output::SendEventOutput;
+use crate::error::{NostrSdkError, Result};
+use crate::protocol::event::{Event, EventBuilder};
+use crate::protocol::key::PublicKey;
+use crate::protocol::nips::nip02::ContactList;
+use crate::protocol::types::Contact;
+
+impl Client {
+    /// Fetch the latest contact list of the signer user from its write relays (NIP-65)
+    ///
+    /// Falls back to the pool relays if the user has no relay list.
+    /// The write relays are added to the pool with `READ` and `WRITE` capabilities.
+    /// The newest of the fetched and the locally stored contact lists is taken.
+    /// Returns the contact list and the write relays.
+    async fn fetch_latest_contact_list(
+        &self,
+        timeout: Duration,
+    ) -> Result<(ContactList, Vec<nostr::RelayUrl>)> {
+        let public_key: nostr::PublicKey = **self
+            .signer()?
+            .get_public_key_async()
+            .await?
+            .ok_or_else(|| NostrSdkError::Signer {
+                msg: String::from("Received None instead of public key"),
+            })?;
+
+        // Write relays
+        let filter: nostr::Filter = nostr::Filter::new()
+            .kind(nostr::Kind::RelayList)
+            .author(public_key)
+            .limit(1);
+        let events = self
+            .inner
+            .fetch_events(client::ReqTarget::auto([filter]))
+            .timeout(timeout)
+            .await?;
+        let relays: Vec<nostr::RelayUrl> = match events.into_iter().max_by_key(|e| e.created_at) {
+            Some(event) => nip65::extract_relay_list(&event)
+                .filter(|(.., metadata)| {
+                    matches!(metadata, None | Some(nip65::RelayMetadata::Write))
+                })
+                .map(|(url, ..)| url.clone())
+                .collect(),
+            None => Vec::new(),
+        };
+
+        for url in relays.iter() {
+            if self.inner.relay(url).await?.is_none() {
+                self.inner
+                    .add_relay(url)
+                    .capabilities(relay::RelayCapabilities::READ | relay::RelayCapabilities::WRITE)
+                    .await?;
+            }
+        }
+
+        if !relays.is_empty() {
+            self.inner.connect().await;
+        }
+
+        // Contact list
+        let filter: nostr::Filter = nostr::Filter::new()
+            .kind(nostr::Kind::ContactList)
+            .author(public_key)
+            .limit(1);
+        let target: client::ReqTarget = if relays.is_empty() {
+            client::ReqTarget::auto([filter.clone()])
+        } else {
+            let targets: HashMap<nostr::RelayUrl, Vec<nostr::Filter>> = relays
+                .iter()
+                .cloned()
+                .map(|url| (url, vec![filter.clone()]))
+                .collect();
+            client::ReqTarget::manual(targets)
+        };
+        let events = self.inner.fetch_events(target).timeout(timeout).await?;
+
+        // The local database may have a newer contact list than the relays
+        let local = self.inner.database().query(filter).await?;
+
+        // Newest contact list: on a `created_at` tie, the lowest ID wins (NIP-01)
+        match events
+            .into_iter()
+            .chain(local)
+            .max_by(|a, b| a.created_at.cmp(&b.created_at).then(b.id.cmp(&a.id)))
+        {
+            Some(event) => Ok((ContactList::from_event(&Event::from(event))?, relays)),
+            None => Err(NostrSdkError::Relay {
+                msg: String::from(
+                    "Contact list not found: refusing to publish a new one, to not overwrite the existing follows",
+                ),
+            }),
+        }
+    }
+
+    /// Sign the updated contact list and send it to the write relays
+    ///
+    /// Falls back to the default target of [`Client::send_event`] if the user has no write relays.
+    async fn send_contact_list(
+        &self,
+        builder: &EventBuilder,
+        relays: Vec<nostr::RelayUrl>,
+    ) -> Result<SendEventOutput> {
+        if relays.is_empty() {
+            return self.send_event_builder(builder).await;
+        }
+
+        let event: Event = builder.finalize_async(self.signer()?).await?;
+        let target = SendEventTarget::to(relays.into_iter().map(|u| Arc::new(u.into())).collect());
+        self.send_event(&event, Some(Arc::new(target)), None, None, None)
+            .await
+    }
+}
+
+#[uniffi::export(async_runtime = "tokio")]
+impl Client {
+    /// Follow a public key
+    ///
+    /// The latest contact list is fetched from the write relays of the user and updated,
+    /// preserving the existing contacts, then sent to the write relays.
+    /// Fails, without publishing, if the contact list can't be retrieved.
+    ///
+    /// Returns `None`, without publishing, if the public key is already followed.
+    ///
+    /// <https://github.com/nostr-protocol/nips/blob/master/02.md>
+    #[uniffi::method(default(timeout = None))]
+    pub async fn follow(
+        &self,
+        public_key: &PublicKey,
+        timeout: Option<Duration>,
+    ) -> Result<Option<SendEventOutput>> {
+        let (contact_list, relays) = self
+            .fetch_latest_contact_list(timeout.unwrap_or(Duration::from_secs(10)))
+            .await?;
+
+        if contact_list.is_following(public_key) {
+            return Ok(None);
+        }
+
+        let builder: EventBuilder = contact_list.follow(Contact {
+            public_key: Arc::new(PublicKey::from(**public_key)),
+            relay_url: None,
+            alias: None,
+        })?;
+        Ok(Some(self.send_contact_list(&builder, relays).await?))
+    }
+
+    /// Unfollow a public key
+    ///
+    /// The latest contact list is fetched from the write relays of the user, updated
+    /// and sent to the write relays.
+    /// Fails, without publishing, if the contact list can't be retrieved.
+    ///
+    /// <https://github.com/nostr-protocol/nips/blob/master/02.md>
+    #[uniffi::method(default(timeout = None))]
+    pub async fn unfollow(
+        &self,
+        public_key: &PublicKey,
+        timeout: Option<Duration>,
+    ) -> Result<SendEventOutput> {
+        let (contact_list, relays) = self
+            .fetch_latest_contact_list(timeout.unwrap_or(Duration::from_secs(10)))
+            .await?;
+        self.send_contact_list(&contact_list.unfollow(public_key), relays)
+            .await
+    }
+}
@@ -1 +1,3 @@
+mod contacts;
+mod publish;
 mod send_event;
@@ -0,0 +1,171 @@
+#[cfg(feature = "nip59")]
+use std::ops::Deref;
+use std::sync::Arc;
+#[cfg(feature = "nip59")]
+use std::time::Duration;
+
+#[cfg(feature = "nip59")]
+use super::send_event::SendEventTarget;
+use crate::client::Client;
+use crate::client::output::SendEventOutput;
+use crate::error::{NostrSdkError, Result};
+#[cfg(feature = "nip59")]
+use crate::protocol::event::UnsignedEvent;
+use crate::protocol::event::{Event, EventBuilder, Tag};
+#[cfg(feature = "nip59")]
+use crate::protocol::key::PublicKey;
+use crate::protocol::nips::nip01::Metadata;
+use crate::protocol::nips::nip09::EventDeletionRequest;
+#[cfg(feature = "nip59")]
+use crate::protocol::nips::nip59::nip59_make_gift_wrap_async;
+use crate::protocol::signer::AsyncNostrSigner;
+use crate::protocol::types::RelayUrl;
+
+#[uniffi::export(async_runtime = "tokio")]
+impl Client {
+    /// Check if a signer is set
+    pub fn has_signer(&self) -> bool {
+        self.signer.is_some()
+    }
+
+    /// Get the signer set with [`ClientBuilder::signer`]
+    pub fn signer(&self) -> Result<Arc<dyn AsyncNostrSigner>> {
+        self.signer.clone().ok_or_else(|| NostrSdkError::Signer {
+            msg: String::from("Signer not configured: set it with ClientBuilder::signer"),
+        })
+    }
+
+    /// Sign the event builder with the client signer and send the event
+    ///
+    /// The event is sent with the default target of [`Client::send_event`].
+    pub async fn send_event_builder(&self, builder: &EventBuilder) -> Result<SendEventOutput> {
+        let event: Event = builder.finalize_async(self.signer()?).await?;
+        self.send_event(&event, None, None, None, None).await
+    }
+
+    /// Set profile metadata
+    ///
+    /// <https://github.com/nostr-protocol/nips/blob/master/01.md>
+    pub async fn set_metadata(&self, metadata: &Metadata) -> Result<SendEventOutput> {
+        self.send_event_builder(&EventBuilder::metadata(metadata))
+            .await
+    }
+
+    /// Publish text note
+    ///
+    /// <https://github.com/nostr-protocol/nips/blob/master/01.md>
+    #[uniffi::method(default(tags = []))]
+    pub async fn publish_text_note(
+        &self,
+        content: &str,
+        tags: Vec<Arc<Tag>>,
+    ) -> Result<SendEventOutput> {
+        let builder: EventBuilder = EventBuilder::text_note(content).tags(&tags);
+        self.send_event_builder(&builder).await
+    }
+
+    /// React to an event
+    ///
+    /// <https://github.com/nostr-protocol/nips/blob/master/25.md>
+    pub async fn react(&self, event: &Event, reaction: &str) -> Result<SendEventOutput> {
+        self.send_event_builder(&EventBuilder::reaction(event, reaction))
+            .await
+    }
+
+    /// Repost an event
+    ///
+    /// <https://github.com/nostr-protocol/nips/blob/master/18.md>
+    #[uniffi::method(default(relay_url = None))]
+    pub async fn repost(
+        &self,
+        event: &Event,
+        relay_url: Option<Arc<RelayUrl>>,
+    ) -> Result<SendEventOutput> {
+        self.send_event_builder(&EventBuilder::repost(event, relay_url)?)
+            .await
+    }
+
+    /// Send an event deletion request
+    ///
+    /// <https://github.com/nostr-protocol/nips/blob/master/09.md>
+    pub async fn delete_event(&self, request: EventDeletionRequest) -> Result<SendEventOutput> {
+        self.send_event_builder(&EventBuilder::delete(request))
+            .await
+    }
+}
+
+#[cfg(feature = "nip59")]
+#[uniffi::export(async_runtime = "tokio")]
+impl Client {
+    /// Send a private direct message
+    ///
+    /// The gift wrap is sent to the NIP-17 relays of the receiver.
+    /// A copy of the message is also gift wrapped to the sender and sent to the sender's NIP-17 relays,
+    /// so that it's available to the sender's other devices.
+    ///
+    /// The output refers to the gift wrap sent to the receiver.
+    ///
+    /// <https://github.com/nostr-protocol/nips/blob/master/17.md>
+    #[uniffi::method(default(expiration = None, rumor_extra_tags = []))]
+    pub async fn send_private_msg(
+        &self,
+        receiver: &PublicKey,
+        message: &str,
+        expiration: Option<Duration>,
+        rumor_extra_tags: Vec<Arc<Tag>>,
+    ) -> Result<SendEventOutput> {
+        let signer: Arc<dyn AsyncNostrSigner> = self.signer()?;
+        let sender: Arc<PublicKey> =
+            signer
+                .get_public_key_async()
+                .await?
+                .ok_or_else(|| NostrSdkError::Signer {
+                    msg: String::from("Received None instead of public key"),
+                })?;
+
+        let rumor: UnsignedEvent =
+            nostr::EventBuilder::new(nostr::Kind::PrivateDirectMessage, message)
+                .tag(nostr::Tag::public_key(**receiver))
+                .tags(
+                    rumor_extra_tags
+                        .into_iter()
+                        .map(|t| t.as_ref().deref().clone()),
+                )
+                .finalize_unsigned(**sender)
+                .into();
+
+        let gift_wrap: Event =
+            nip59_make_gift_wrap_async(signer.clone(), receiver, &rumor, expiration, Vec::new())
+                .await?;
+        let output: SendEventOutput = self
+            .send_event(
+                &gift_wrap,
+                Some(Arc::new(SendEventTarget::to_nip17())),
+                None,
+                None,
+                None,
+            )
+            .await?;
+
+        // Sender copy
+        if **sender != **receiver {
+            let gift_wrap: Event =
+                nip59_make_gift_wrap_async(signer, &sender, &rumor, expiration, Vec::new()).await?;
+
+            if let Err(e) = self
+                .send_event(
+                    &gift_wrap,
+                    Some(Arc::new(SendEventTarget::to_nip17())),
+                    None,
+                    None,
+                    None,
+                )
+                .await
+            {
+                tracing::warn!(error = %e, "Failed to send the sender copy of the private message");
+            }
+        }
+
+        Ok(output)
+    }
+}
@@ -94,6 +94,13 @@ impl AckPolicy {
 #[uniffi::export(async_runtime = "tokio")]
 impl Client {
     /// Send event
+    ///
+    /// If a [`PowRetryPolicy`] is set in the [`ClientBuilder`], the relays that rejected the event
+    /// with a `pow:` message are retried with a mined event (see [`SendEventOutput::mined_event`]).
+    /// Retry failures are recorded in the `failed` map of the output.
+    ///
+    /// If the outbox is enabled ([`ClientBuilder::outbox`]), the relays that failed with a
+    /// transient error are retried in background.
     #[uniffi::method(default(target = None, ack_policy = None, ok_timeout = None, authentication_timeout = None))]
     pub async fn send_event(
         &self,
@@ -133,6 +140,32 @@ impl Client {
             builder = builder.authentication_timeout(authentication_timeout);
         }
 
-        Ok(builder.await?.into())
+        let mut output: SendEventOutput = builder.await?.into();
+
+        if let Some(policy) = &self.pow_retry {
+            output = self
+                .retry_with_pow(
+                    policy,
+                    event.deref(),
+                    output,
+                    ok_timeout,
+                    authentication_timeout,
+                )
+                .await;
+        }
+
+        if self.outbox.is_some() {
+            let queued: &nostr::Event = match &output.mined_event {
+                Some(mined) => mined.as_ref().deref(),
+                None => event.deref(),
+            };
+
+            // The event has already been sent: don't discard the output
+            if let Err(e) = self.enqueue_outbox(queued, &output).await {
+                tracing::error!(error = %e, "Failed to queue the event in the outbox");
+            }
+        }
+
+        Ok(output)
     }
 }
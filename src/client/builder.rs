@@ -10,13 +10,16 @@ use std::time::Duration;
 use nostr_sdk::{client, prelude};
 use uniffi::{Enum, Object, Record};
 
-use super::Client;
-use crate::authenticator::{Authenticator, FFI2RustAuthenticator};
+use super::outbox::Outbox;
+use super::{Client, GossipSelection, PowRetryPolicy};
+use crate::authenticator::{Authenticator, FFI2RustAuthenticator, SentAuthEvents};
 use crate::database::NostrDatabase;
 use crate::error::{NostrSdkError, Result};
-use crate::gossip::NostrGossip;
+use crate::gossip::{GossipAllowedRelays, NostrGossip};
 use crate::monitor::Monitor;
 use crate::policy::{AdmitPolicy, FFI2RustAdmitPolicy};
+use crate::protocol::key::SecretKey;
+use crate::protocol::signer::AsyncNostrSigner;
 #[cfg(not(target_arch = "wasm32"))]
 use crate::proxy::Proxy;
 use crate::relay::RelayLimits;
@@ -55,30 +58,6 @@ impl From<GossipRelayLimits> for client::GossipRelayLimits {
     }
 }
 
-/// Allowed gossip relay types during selection
-#[derive(Record)]
-pub struct GossipAllowedRelays {
-    /// Allow tor onion relays (default: true)
-    #[uniffi(default = true)]
-    pub onion: bool,
-    /// Allow local network relays (default: false)
-    #[uniffi(default = false)]
-    pub local: bool,
-    /// Allow relays without SSL/TLS encryption (default: true)
-    #[uniffi(default = true)]
-    pub without_tls: bool,
-}
-
-impl From<GossipAllowedRelays> for prelude::GossipAllowedRelays {
-    fn from(allowed: GossipAllowedRelays) -> Self {
-        Self {
-            onion: allowed.onion,
-            local: allowed.local,
-            without_tls: allowed.without_tls,
-        }
-    }
-}
-
 /// Background gossip refresh configuration.
 #[derive(Clone, Object)]
 pub struct GossipBackgroundRefresh {
@@ -128,6 +107,8 @@ impl GossipBackgroundRefresh {
 #[derive(Clone, Object)]
 pub struct GossipConfig {
     inner: client::GossipConfig,
+    limits: client::GossipRelayLimits,
+    allowed: prelude::GossipAllowedRelays,
 }
 
 impl Deref for GossipConfig {
@@ -140,7 +121,11 @@ impl Deref for GossipConfig {
 
 impl From<client::GossipConfig> for GossipConfig {
     fn from(inner: client::GossipConfig) -> Self {
-        Self { inner }
+        Self {
+            inner,
+            limits: client::GossipRelayLimits::default(),
+            allowed: prelude::GossipAllowedRelays::default(),
+        }
     }
 }
 
@@ -148,22 +133,22 @@ impl From<client::GossipConfig> for GossipConfig {
 impl GossipConfig {
     #[uniffi::constructor]
     pub fn new() -> Self {
-        Self {
-            inner: client::GossipConfig::default(),
-        }
+        client::GossipConfig::default().into()
     }
 
     /// Max number of gossip relays to use
     pub fn limits(&self, limits: GossipRelayLimits) -> Self {
         let mut builder = self.clone();
-        builder.inner = builder.inner.limits(limits.into());
+        builder.limits = limits.into();
+        builder.inner = builder.inner.limits(builder.limits);
         builder
     }
 
     /// Allowed relays during gossip selection
     pub fn allowed(&self, allowed: GossipAllowedRelays) -> Self {
         let mut builder = self.clone();
-        builder.inner = builder.inner.allowed(allowed.into());
+        builder.allowed = allowed.into();
+        builder.inner = builder.inner.allowed(builder.allowed);
         builder
     }
 
@@ -237,11 +222,33 @@ impl From<SleepWhenIdle> for client::SleepWhenIdle {
 #[derive(Clone, Default, Object)]
 pub struct ClientBuilder {
     inner: client::ClientBuilder,
+    auth_events: SentAuthEvents,
+    persist_subscriptions: bool,
+    local_store_secret: Option<nostr::SecretKey>,
+    signer: Option<Arc<dyn AsyncNostrSigner>>,
+    rumor_cache_key: Option<nostr::SecretKey>,
+    pow_retry: Option<Arc<PowRetryPolicy>>,
+    outbox: bool,
+    gossip: Option<Arc<dyn nostr_gossip::NostrGossip>>,
+    gossip_limits: client::GossipRelayLimits,
+    gossip_allowed: prelude::GossipAllowedRelays,
 }
 
 impl From<client::ClientBuilder> for ClientBuilder {
     fn from(inner: client::ClientBuilder) -> Self {
-        Self { inner }
+        Self {
+            inner,
+            auth_events: SentAuthEvents::default(),
+            persist_subscriptions: false,
+            local_store_secret: None,
+            signer: None,
+            rumor_cache_key: None,
+            pow_retry: None,
+            outbox: false,
+            gossip: None,
+            gossip_limits: client::GossipRelayLimits::default(),
+            gossip_allowed: prelude::GossipAllowedRelays::default(),
+        }
     }
 }
 
@@ -269,11 +276,33 @@ impl ClientBuilder {
         let mut builder = self.clone();
         let authenticator = FFI2RustAuthenticator {
             inner: authenticator,
+            sent: builder.auth_events.clone(),
         };
         builder.inner = builder.inner.authenticator(authenticator);
         builder
     }
 
+    /// Set a signer
+    ///
+    /// The signer is used by the publish helpers (i.e., [`Client::publish_text_note`]).
+    pub fn signer(&self, signer: Arc<dyn AsyncNostrSigner>) -> Self {
+        let mut builder = self.clone();
+        builder.signer = Some(signer);
+        builder
+    }
+
+    /// Cache the unwrapped gift wrap rumors, NIP-44 encrypted with a local key
+    ///
+    /// The cache is used by [`Client::unwrap_gift_wrap`] and the [`PrivateMessageInbox`](crate::client::PrivateMessageInbox).
+    /// Without a key, the rumors are not cached. The records encrypted with another key are ignored.
+    ///
+    /// Requires [`ClientBuilder::local_store_secret`].
+    pub fn rumor_cache_key(&self, key: &SecretKey) -> Self {
+        let mut builder = self.clone();
+        builder.rumor_cache_key = Some(key.deref().clone());
+        builder
+    }
+
     pub fn database(&self, database: &NostrDatabase) -> Self {
         let mut builder = self.clone();
         builder.inner = builder.inner.database(database.deref().clone());
@@ -284,6 +313,7 @@ impl ClientBuilder {
     pub fn gossip(&self, gossip: &NostrGossip) -> Self {
         let mut builder = self.clone();
         builder.inner = builder.inner.gossip(gossip.deref().clone());
+        builder.gossip = Some(gossip.deref().clone());
         builder
     }
 
@@ -374,13 +404,92 @@ impl ClientBuilder {
     pub fn gossip_config(&self, config: &GossipConfig) -> Self {
         let mut builder = self.clone();
         builder.inner = builder.inner.gossip_config(config.deref().clone());
+        builder.gossip_limits = config.limits;
+        builder.gossip_allowed = config.allowed;
+        builder
+    }
+
+    /// Per-install secret of the local records (i.e., persisted subscriptions, outbox, rumor cache)
+    ///
+    /// The records are saved into the database, signed by a key derived from this secret.
+    /// Generate it once and keep it in the platform secure storage: records written with another secret are ignored.
+    pub fn local_store_secret(&self, secret: &SecretKey) -> Self {
+        let mut builder = self.clone();
+        builder.local_store_secret = Some(secret.deref().clone());
+        builder
+    }
+
+    /// Persist the subscriptions into the database (default: false)
+    ///
+    /// The subscriptions without auto-close conditions are saved, until unsubscribed,
+    /// and can be restored after a restart with [`Client::restore_subscriptions`].
+    ///
+    /// Requires [`ClientBuilder::local_store_secret`].
+    pub fn persist_subscriptions(&self, enable: bool) -> Self {
+        let mut builder = self.clone();
+        builder.persist_subscriptions = enable;
+        builder
+    }
+
+    /// Mine and re-send the events rejected by relays requiring PoW (NIP-13)
+    ///
+    /// Applied by [`Client::send_event`]. Disabled by default.
+    pub fn pow_retry(&self, policy: Arc<PowRetryPolicy>) -> Self {
+        let mut builder = self.clone();
+        builder.pow_retry = Some(policy);
+        builder
+    }
+
+    /// Persist the events that failed to be sent and retry them when the relays are connected (default: false)
+    ///
+    /// Only the relays that failed with a transient error (i.e., disconnected, `rate-limited:`, `auth-required:`) are retried.
+    /// See [`Client::pending_outbox`] and [`Client::outbox_notifications`].
+    ///
+    /// Requires [`ClientBuilder::local_store_secret`].
+    pub fn outbox(&self, enable: bool) -> Self {
+        let mut builder = self.clone();
+        builder.outbox = enable;
         builder
     }
 
     /// Build [`Client`]
-    pub fn build(&self) -> Client {
+    ///
+    /// Fails if [`ClientBuilder::persist_subscriptions`], [`ClientBuilder::outbox`] or [`ClientBuilder::rumor_cache_key`]
+    /// are set without [`ClientBuilder::local_store_secret`].
+    pub fn build(&self) -> Result<Client> {
+        if self.persist_subscriptions && self.local_store_secret.is_none() {
+            return Err(NostrSdkError::Generic(String::from(
+                "Persistent subscriptions require `ClientBuilder::local_store_secret`",
+            )));
+        }
+
+        if self.outbox && self.local_store_secret.is_none() {
+            return Err(NostrSdkError::Generic(String::from(
+                "Outbox requires `ClientBuilder::local_store_secret`",
+            )));
+        }
+
+        if self.rumor_cache_key.is_some() && self.local_store_secret.is_none() {
+            return Err(NostrSdkError::Generic(String::from(
+                "Rumor cache requires `ClientBuilder::local_store_secret`",
+            )));
+        }
+
         let inner = self.inner.clone();
-        inner.build().into()
+        let mut client: Client = inner.build().into();
+        client.auth_events = self.auth_events.clone();
+        client.persist_subscriptions = self.persist_subscriptions;
+        client.local_store_secret = self.local_store_secret.clone();
+        client.signer = self.signer.clone();
+        client.rumor_cache_key = self.rumor_cache_key.clone();
+        client.pow_retry = self.pow_retry.clone();
+        client.outbox = self.outbox.then(|| Arc::new(Outbox::new()));
+        client.gossip = self.gossip.clone().map(|store| GossipSelection {
+            store,
+            limits: self.gossip_limits,
+            allowed: self.gossip_allowed,
+        });
+        Ok(client)
     }
 }
 
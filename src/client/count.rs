@@ -0,0 +1,219 @@
+// Copyright (c) 2023-2025 Rust Nostr Developers
+// Distributed under the MIT software license
+
+//! NIP-45 COUNT across multiple relays
+
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::sync::Arc;
+use std::time::Duration;
+
+use futures_util::future;
+use nostr::filter::{Alphabet, SingleLetterTag};
+use nostr_sdk::{client, prelude, relay};
+use uniffi::Record;
+
+use super::Client;
+use super::req_target::{RelayTargets, ReqTarget};
+use crate::error::Result;
+use crate::protocol::types::RelayUrl;
+
+/// Count output
+#[derive(Record)]
+pub struct CountOutput {
+    /// Aggregate count
+    ///
+    /// The max of the relay counts.
+    pub count: u64,
+    /// Whether the aggregate count is approximate
+    ///
+    /// The count is approximate if the relays disagree, some relays failed
+    /// or a relay was queried with more than one filter (counts are summed).
+    pub approximate: bool,
+    /// Counts returned by each relay
+    pub relays: HashMap<Arc<RelayUrl>, u64>,
+    /// Map of relays that failed, with related errors.
+    pub failed: HashMap<Arc<RelayUrl>, String>,
+}
+
+/// Gossip store and selection settings, used to resolve automatic targets
+pub(super) struct GossipSelection {
+    pub(super) store: Arc<dyn nostr_gossip::NostrGossip>,
+    pub(super) limits: client::GossipRelayLimits,
+    pub(super) allowed: prelude::GossipAllowedRelays,
+}
+
+impl GossipSelection {
+    /// Outbox relays of the authors and inbox relays of the `p` tagged public keys
+    async fn relays(&self, filters: &[nostr::Filter]) -> Result<HashSet<nostr::RelayUrl>> {
+        let p: SingleLetterTag = SingleLetterTag::lowercase(Alphabet::P);
+
+        let mut authors: BTreeSet<nostr::PublicKey> = BTreeSet::new();
+        let mut mentioned: BTreeSet<nostr::PublicKey> = BTreeSet::new();
+
+        for filter in filters.iter() {
+            if let Some(list) = &filter.authors {
+                authors.extend(list.iter().copied());
+            }
+
+            if let Some(values) = filter.generic_tags.get(&p) {
+                mentioned.extend(
+                    values
+                        .iter()
+                        .filter_map(|v| nostr::PublicKey::from_hex(v).ok()),
+                );
+            }
+        }
+
+        let mut relays: HashSet<nostr::RelayUrl> = HashSet::new();
+
+        for public_key in authors.iter() {
+            let selection = nostr_gossip::BestRelaySelection::Write {
+                limit: self.limits.write_relays_per_user as usize,
+            };
+            relays.extend(
+                self.store
+                    .get_best_relays(public_key, selection, self.allowed)
+                    .await?,
+            );
+        }
+
+        for public_key in mentioned.iter() {
+            let selection = nostr_gossip::BestRelaySelection::Read {
+                limit: self.limits.read_relays_per_user as usize,
+            };
+            relays.extend(
+                self.store
+                    .get_best_relays(public_key, selection, self.allowed)
+                    .await?,
+            );
+        }
+
+        Ok(relays)
+    }
+}
+
+async fn count_relay(
+    relay: relay::Relay,
+    filters: Vec<nostr::Filter>,
+    connect: bool,
+    timeout: Duration,
+) -> Result<u64> {
+    if connect {
+        relay.try_connect().timeout(timeout).await?;
+    }
+
+    let counts = future::try_join_all(
+        filters
+            .into_iter()
+            .map(|filter| relay.count_events(filter, timeout)),
+    )
+    .await?;
+    Ok(counts.into_iter().map(|c| c as u64).sum())
+}
+
+#[uniffi::export(async_runtime = "tokio")]
+impl Client {
+    /// Count events (NIP-45)
+    ///
+    /// Send a `COUNT` request to each relay of the target, in parallel.
+    /// With an automatic target, the client relays are used. If gossip is enabled
+    /// ([`ClientBuilder::gossip`]), the NIP-65 relays of the public keys in the filters
+    /// (authors and `p` tags) are also queried, and added to the pool with `GOSSIP` capability.
+    ///
+    /// The aggregate count is the max of the relay counts:
+    /// relays may count the same events, so the counts are never summed.
+    ///
+    /// HyperLogLog values (NIP-45 `hll`) are not merged: the relay pool parses the `COUNT`
+    /// responses without them, so they can't be captured yet.
+    pub async fn count_events(&self, target: &ReqTarget, timeout: Duration) -> Result<CountOutput> {
+        let (targets, added) = match target.targets() {
+            RelayTargets::Auto(filters) => self.auto_count_targets(filters).await?,
+            RelayTargets::Manual(targets) => (targets.clone(), HashSet::new()),
+        };
+
+        let mut approximate: bool = targets.values().any(|filters| filters.len() > 1);
+        let mut failed: HashMap<Arc<RelayUrl>, String> = HashMap::new();
+        let mut requests = Vec::with_capacity(targets.len());
+
+        for (url, filters) in targets.into_iter() {
+            match self.inner.relay(&url).await? {
+                Some(relay) => {
+                    let connect: bool = added.contains(&url);
+                    requests.push(async move {
+                        let res = count_relay(relay, filters, connect, timeout).await;
+                        (url, res)
+                    })
+                }
+                None => {
+                    failed.insert(Arc::new(url.into()), String::from("Relay not found"));
+                }
+            }
+        }
+
+        let mut relays: HashMap<Arc<RelayUrl>, u64> = HashMap::with_capacity(requests.len());
+
+        for (url, res) in future::join_all(requests).await.into_iter() {
+            match res {
+                Ok(count) => {
+                    relays.insert(Arc::new(url.into()), count);
+                }
+                Err(e) => {
+                    failed.insert(Arc::new(url.into()), e.to_string());
+                }
+            }
+        }
+
+        let count: u64 = relays.values().copied().max().unwrap_or_default();
+
+        if !failed.is_empty() || relays.values().any(|c| *c != count) {
+            approximate = true;
+        }
+
+        Ok(CountOutput {
+            count,
+            approximate,
+            relays,
+            failed,
+        })
+    }
+}
+
+impl Client {
+    /// Relays and filters of an automatic target, with the gossip relays added to the pool
+    async fn auto_count_targets(
+        &self,
+        filters: &[nostr::Filter],
+    ) -> Result<(
+        HashMap<nostr::RelayUrl, Vec<nostr::Filter>>,
+        HashSet<nostr::RelayUrl>,
+    )> {
+        let mut targets: HashMap<nostr::RelayUrl, Vec<nostr::Filter>> = self
+            .inner
+            .relays()
+            .await
+            .into_keys()
+            .map(|url| (url, filters.to_vec()))
+            .collect();
+        let mut added: HashSet<nostr::RelayUrl> = HashSet::new();
+
+        if let Some(gossip) = &self.gossip {
+            for url in gossip.relays(filters).await?.into_iter() {
+                if targets.contains_key(&url) {
+                    continue;
+                }
+
+                if self.inner.relay(&url).await?.is_none() {
+                    self.inner
+                        .add_relay(&url)
+                        .capabilities(relay::RelayCapabilities::GOSSIP)
+                        .await?;
+                    added.insert(url.clone());
+                }
+
+                targets.insert(url, filters.to_vec());
+            }
+        }
+
+        Ok((targets, added))
+    }
+}
@@ -0,0 +1,156 @@
+// Copyright (c) 2023-2025 Rust Nostr Developers
+// Distributed under the MIT software license
+
+//! Gap filling on relay reconnection
+//!
+//! Track the newest event received from each relay for a subscription and,
+//! when the relay reconnects, re-send the `REQ` with `since` set to that timestamp.
+
+use std::collections::HashMap;
+
+use async_utility::task;
+use futures_util::future::{AbortHandle, Abortable};
+use futures_util::stream::BoxStream;
+use futures_util::{StreamExt, stream};
+use nostr::SubscriptionId;
+use nostr_sdk::{client, monitor, relay};
+use tokio::sync::broadcast::error::RecvError;
+
+use super::Client;
+use crate::error::{NostrSdkError, Result};
+
+enum GapFillEvent {
+    Client(client::ClientNotification),
+    Monitor(monitor::MonitorNotification),
+}
+
+impl Client {
+    /// Start a task that fills the gaps of the subscription on relay reconnection
+    ///
+    /// Must be called before sending the `REQ`, to not miss the first events.
+    /// The task terminates when the subscription is closed (see [`Client::stop_gap_filler`]) or the client shutdowns.
+    pub(super) async fn start_gap_filler(&self, id: SubscriptionId) -> Result<()> {
+        let monitor: &monitor::Monitor =
+            self.inner.monitor().ok_or_else(|| NostrSdkError::Relay {
+                msg: String::from(
+                    "Gap filling requires a monitor: set it with ClientBuilder::monitor",
+                ),
+            })?;
+
+        let receiver = monitor.subscribe();
+        let monitor_stream = stream::unfold(receiver, |mut receiver| async move {
+            loop {
+                match receiver.recv().await {
+                    Ok(notification) => return Some((notification, receiver)),
+                    Err(RecvError::Lagged(..)) => continue,
+                    Err(RecvError::Closed) => return None,
+                }
+            }
+        });
+
+        let events: BoxStream<'static, GapFillEvent> = stream::select(
+            self.inner.notifications().map(GapFillEvent::Client).boxed(),
+            monitor_stream.map(GapFillEvent::Monitor).boxed(),
+        )
+        .boxed();
+
+        let (handle, registration) = AbortHandle::new_pair();
+        let filler = Abortable::new(
+            fill_gaps(self.inner.clone(), id.clone(), events),
+            registration,
+        );
+
+        let mut gap_fillers = self.gap_fillers.lock().await;
+        if let Some(previous) = gap_fillers.insert(id, handle) {
+            previous.abort();
+        }
+
+        task::spawn(async move {
+            let _ = filler.await;
+        });
+
+        Ok(())
+    }
+
+    /// Stop the gap filling task of the subscription, if any
+    pub(super) async fn stop_gap_filler(&self, id: &SubscriptionId) {
+        let mut gap_fillers = self.gap_fillers.lock().await;
+        if let Some(handle) = gap_fillers.remove(id) {
+            handle.abort();
+        }
+    }
+
+    /// Stop all the gap filling tasks
+    pub(super) async fn stop_all_gap_fillers(&self) {
+        let mut gap_fillers = self.gap_fillers.lock().await;
+        for (.., handle) in gap_fillers.drain() {
+            handle.abort();
+        }
+    }
+}
+
+async fn fill_gaps(
+    inner: client::Client,
+    id: SubscriptionId,
+    mut events: BoxStream<'static, GapFillEvent>,
+) {
+    let mut last_seen: HashMap<nostr::RelayUrl, nostr::Timestamp> = HashMap::new();
+
+    while let Some(event) = events.next().await {
+        match event {
+            GapFillEvent::Client(client::ClientNotification::Message { relay_url, message }) => {
+                if let nostr::RelayMessage::Event {
+                    subscription_id,
+                    event,
+                } = message.as_ref()
+                {
+                    if subscription_id.as_ref() == &id {
+                        let newest = last_seen.entry(relay_url).or_insert(event.created_at);
+
+                        if event.created_at > *newest {
+                            *newest = event.created_at;
+                        }
+                    }
+                }
+            }
+            GapFillEvent::Client(client::ClientNotification::Shutdown) => break,
+            GapFillEvent::Client(..) => {}
+            GapFillEvent::Monitor(monitor::MonitorNotification::StatusChanged {
+                relay_url,
+                status: relay::RelayStatus::Connected,
+            }) => {
+                let mut targets = inner.subscription(&id).await;
+
+                // Subscription closed
+                if targets.is_empty() {
+                    break;
+                }
+
+                let (Some(since), Some(filters)) =
+                    (last_seen.get(&relay_url), targets.remove(&relay_url))
+                else {
+                    continue;
+                };
+
+                // Without `limit`, otherwise only the newest events of the gap are returned
+                let filters: Vec<nostr::Filter> = filters
+                    .into_iter()
+                    .map(|mut f| {
+                        let since = f.since.map_or(*since, |s| s.max(*since));
+                        f.limit = None;
+                        f.since(since)
+                    })
+                    .collect();
+
+                if let Err(e) = inner
+                    .subscribe(client::ReqTarget::single(relay_url.clone(), filters))
+                    .with_id(id.clone())
+                    .await
+                {
+                    tracing::warn!(%id, url = %relay_url, error = %e, "Can't re-request the missed events");
+                }
+            }
+            GapFillEvent::Monitor(..) => {}
+        }
+    }
+}
@@ -0,0 +1,395 @@
+// Copyright (c) 2023-2025 Rust Nostr Developers
+// Distributed under the MIT software license
+
+//! NIP-17 private messages inbox
+
+use std::collections::{BTreeSet, HashMap};
+use std::pin::Pin;
+use std::sync::Arc;
+use std::time::Duration;
+
+use futures_util::{Stream, StreamExt, future};
+use nostr::SubscriptionId;
+use nostr::key::AsyncGetPublicKey;
+use nostr::nips::{nip17, nip59};
+use nostr_sdk::{client, relay};
+use tokio::sync::{Mutex, RwLock};
+use uniffi::{Object, Record};
+
+use super::Client;
+use super::rumors::RumorCache;
+use crate::error::{NostrSdkError, Result};
+use crate::protocol::event::{EventId, UnsignedEvent};
+use crate::protocol::key::PublicKey;
+use crate::protocol::signer::{AsyncNostrSigner, IntermediateAsyncNostrSigner};
+use crate::protocol::types::RelayUrl;
+
+/// Gift wraps `created_at` is tweaked up to 2 days in the past
+const GIFT_WRAP_MAX_DELAY: Duration = Duration::from_secs(60 * 60 * 24 * 2);
+
+/// Private message
+#[derive(Record)]
+pub struct PrivateMessage {
+    /// Gift wrap event ID
+    pub gift_wrap_id: Arc<EventId>,
+    /// Sender public key
+    pub sender: Arc<PublicKey>,
+    /// Message rumor (kind `14` or `15`)
+    pub rumor: Arc<UnsignedEvent>,
+}
+
+/// Private messages conversation
+#[derive(Record)]
+pub struct Conversation {
+    /// Participants: rumor authors and `p` tags, the inbox owner included
+    pub participants: Vec<Arc<PublicKey>>,
+    /// Subject of the conversation, from the latest `subject` tag
+    pub subject: Option<String>,
+    /// Messages, from oldest to newest
+    pub messages: Vec<PrivateMessage>,
+}
+
+#[derive(Clone)]
+struct Message {
+    gift_wrap_id: nostr::EventId,
+    sender: nostr::PublicKey,
+    rumor: nostr::UnsignedEvent,
+}
+
+impl From<Message> for PrivateMessage {
+    fn from(message: Message) -> Self {
+        Self {
+            gift_wrap_id: Arc::new(message.gift_wrap_id.into()),
+            sender: Arc::new(message.sender.into()),
+            rumor: Arc::new(message.rumor.into()),
+        }
+    }
+}
+
+impl Message {
+    fn participants(&self) -> BTreeSet<nostr::PublicKey> {
+        let mut participants: BTreeSet<nostr::PublicKey> =
+            self.rumor.tags.public_keys().copied().collect();
+        participants.insert(self.rumor.pubkey);
+        participants
+    }
+
+    fn subject(&self) -> Option<&str> {
+        self.rumor
+            .tags
+            .iter()
+            .find(|t| t.as_slice().first().map(|k| k.as_str()) == Some("subject"))
+            .and_then(|t| t.content())
+    }
+}
+
+struct InboxState {
+    client: client::Client,
+    signer: IntermediateAsyncNostrSigner,
+    public_key: nostr::PublicKey,
+    /// Rumor cache, if the cache key is set
+    cache: Option<RumorCache>,
+    relays: RwLock<Vec<nostr::RelayUrl>>,
+    /// Messages by rumor ID
+    messages: RwLock<HashMap<nostr::EventId, Message>>,
+}
+
+impl InboxState {
+    /// Unwrap a gift wrap and store the message
+    ///
+    /// Returns the message only if it's new.
+    async fn handle(&self, gift_wrap: &nostr::Event) -> Result<Option<Message>> {
+        if gift_wrap.kind != nostr::Kind::GiftWrap {
+            return Ok(None);
+        }
+
+        let nip59::UnwrappedGift { sender, mut rumor } = match &self.cache {
+            Some(cache) => cache.unwrap(&self.signer, gift_wrap).await?,
+            None => nip59::UnwrappedGift::from_gift_wrap_async(&self.signer, gift_wrap).await?,
+        };
+
+        // Private direct messages and file messages
+        if !matches!(rumor.kind.as_u16(), 14 | 15) || rumor.pubkey != sender {
+            return Ok(None);
+        }
+
+        rumor.ensure_id();
+
+        let Some(rumor_id) = rumor.id else {
+            return Ok(None);
+        };
+
+        let mut messages = self.messages.write().await;
+
+        // The same rumor may be wrapped more times (i.e., sender copy)
+        if messages.contains_key(&rumor_id) {
+            return Ok(None);
+        }
+
+        let message: Message = Message {
+            gift_wrap_id: gift_wrap.id,
+            sender,
+            rumor,
+        };
+        messages.insert(rumor_id, message.clone());
+
+        Ok(Some(message))
+    }
+
+    fn filter(&self) -> nostr::Filter {
+        nostr::Filter::new()
+            .kind(nostr::Kind::GiftWrap)
+            .pubkey(self.public_key)
+    }
+}
+
+/// NIP-17 private messages inbox
+///
+/// Receive the gift wraps from the inbox relays (kind `10050`), unwrap them and group the messages by conversation.
+/// If [`ClientBuilder::rumor_cache_key`] is set, the unwrapped rumors are cached, encrypted, in the database,
+/// to avoid decrypting them again.
+///
+/// <https://github.com/nostr-protocol/nips/blob/master/17.md>
+#[derive(Object)]
+pub struct PrivateMessageInbox {
+    state: Arc<InboxState>,
+    subscription: Mutex<Option<SubscriptionId>>,
+}
+
+#[uniffi::export(async_runtime = "tokio")]
+impl PrivateMessageInbox {
+    /// New inbox
+    ///
+    /// If `signer` is not set, the client signer ([`ClientBuilder::signer`]) is used.
+    #[uniffi::constructor(default(signer = None))]
+    pub async fn new(client: &Client, signer: Option<Arc<dyn AsyncNostrSigner>>) -> Result<Self> {
+        let signer: Arc<dyn AsyncNostrSigner> = match signer {
+            Some(signer) => signer,
+            None => client.signer()?,
+        };
+        let signer = IntermediateAsyncNostrSigner::new(signer);
+        let public_key: nostr::PublicKey = signer.get_public_key_async().await?;
+
+        Ok(Self {
+            state: Arc::new(InboxState {
+                client: client.inner.clone(),
+                signer,
+                public_key,
+                cache: client.rumor_cache()?,
+                relays: RwLock::new(Vec::new()),
+                messages: RwLock::new(HashMap::new()),
+            }),
+            subscription: Mutex::new(None),
+        })
+    }
+
+    /// Get the inbox relays
+    ///
+    /// Empty until [`PrivateMessageInbox::sync`] is called.
+    pub async fn relays(&self) -> Vec<Arc<RelayUrl>> {
+        let relays = self.state.relays.read().await;
+        relays.iter().map(|u| Arc::new(u.clone().into())).collect()
+    }
+
+    /// Fetch the inbox relays (kind `10050`) and the gift wraps
+    ///
+    /// The inbox relays are added to the pool, with `READ` capability, if missing.
+    /// The gift wraps already in the database are processed too.
+    ///
+    /// Returns the number of new messages.
+    #[uniffi::method(default(timeout = None))]
+    pub async fn sync(&self, timeout: Option<Duration>) -> Result<u64> {
+        let timeout: Duration = timeout.unwrap_or(Duration::from_secs(10));
+        let state: &InboxState = &self.state;
+
+        // Inbox relays
+        let filter: nostr::Filter = nostr::Filter::new()
+            .kind(nostr::Kind::InboxRelays)
+            .author(state.public_key)
+            .limit(1);
+        let events = state
+            .client
+            .fetch_events(client::ReqTarget::auto([filter]))
+            .timeout(timeout)
+            .await?;
+
+        if let Some(event) = events.into_iter().max_by_key(|e| e.created_at) {
+            let relays: Vec<nostr::RelayUrl> = nip17::extract_relay_list(&event).cloned().collect();
+
+            for url in relays.iter() {
+                if state.client.relay(url).await?.is_none() {
+                    state
+                        .client
+                        .add_relay(url)
+                        .capabilities(relay::RelayCapabilities::READ)
+                        .await?;
+                }
+            }
+
+            state.client.connect().await;
+
+            *state.relays.write().await = relays;
+        }
+
+        let relays: Vec<nostr::RelayUrl> = state.relays.read().await.clone();
+
+        if relays.is_empty() {
+            return Err(NostrSdkError::Relay {
+                msg: String::from("Inbox relays not found: publish a kind 10050 event"),
+            });
+        }
+
+        // Download the gift wraps (saved into the database)
+        let targets: HashMap<nostr::RelayUrl, Vec<nostr::Filter>> = relays
+            .into_iter()
+            .map(|url| (url, vec![state.filter()]))
+            .collect();
+        state
+            .client
+            .fetch_events(client::ReqTarget::manual(targets))
+            .timeout(timeout)
+            .await?;
+
+        // Process the gift wraps of the database
+        let gift_wraps = state.client.database().query(state.filter()).await?;
+        let mut new: u64 = 0;
+
+        for gift_wrap in gift_wraps.into_iter() {
+            // Skip the gift wraps that can't be unwrapped
+            if let Ok(Some(..)) = state.handle(&gift_wrap).await {
+                new += 1;
+            }
+        }
+
+        Ok(new)
+    }
+
+    /// Subscribe to the inbox relays and stream the new messages
+    ///
+    /// Call [`PrivateMessageInbox::sync`] first, to discover the inbox relays.
+    pub async fn subscribe(&self) -> Result<PrivateMessageStream> {
+        let state: Arc<InboxState> = self.state.clone();
+        let relays: Vec<nostr::RelayUrl> = state.relays.read().await.clone();
+
+        if relays.is_empty() {
+            return Err(NostrSdkError::Relay {
+                msg: String::from("Inbox relays unknown: call sync first"),
+            });
+        }
+
+        // Gift wraps may have a `created_at` in the past
+        let since: nostr::Timestamp = nostr::Timestamp::now() - GIFT_WRAP_MAX_DELAY;
+        let targets: HashMap<nostr::RelayUrl, Vec<nostr::Filter>> = relays
+            .into_iter()
+            .map(|url| (url, vec![state.filter().since(since)]))
+            .collect();
+
+        // Listen for notifications before sending the REQ
+        let notifications = state.client.notifications();
+
+        let id: SubscriptionId = state
+            .client
+            .subscribe(client::ReqTarget::manual(targets))
+            .await?
+            .value;
+
+        let mut subscription = self.subscription.lock().await;
+        if let Some(old) = subscription.replace(id.clone()) {
+            state.client.unsubscribe(&old).await?;
+        }
+
+        let stream = notifications
+            .take_while(|n| future::ready(!matches!(n, client::ClientNotification::Shutdown)))
+            .filter_map(move |notification| {
+                let state: Arc<InboxState> = state.clone();
+                let id: SubscriptionId = id.clone();
+                async move {
+                    match notification {
+                        client::ClientNotification::Event {
+                            subscription_id,
+                            event,
+                            ..
+                        } if subscription_id.as_str() == id.as_str() => {
+                            state.handle(&event).await.ok().flatten().map(Into::into)
+                        }
+                        _ => None,
+                    }
+                }
+            })
+            .boxed();
+
+        Ok(PrivateMessageStream {
+            stream: Mutex::new(stream),
+        })
+    }
+
+    /// Close the inbox subscription
+    pub async fn unsubscribe(&self) -> Result<()> {
+        let mut subscription = self.subscription.lock().await;
+        if let Some(id) = subscription.take() {
+            self.state.client.unsubscribe(&id).await?;
+        }
+        Ok(())
+    }
+
+    /// Get the conversations, from the most recent
+    pub async fn conversations(&self) -> Vec<Conversation> {
+        let messages = self.state.messages.read().await;
+
+        let mut groups: HashMap<BTreeSet<nostr::PublicKey>, Vec<Message>> = HashMap::new();
+
+        for message in messages.values() {
+            groups
+                .entry(message.participants())
+                .or_default()
+                .push(message.clone());
+        }
+
+        let mut conversations: Vec<(nostr::Timestamp, Conversation)> = groups
+            .into_iter()
+            .map(|(participants, mut messages)| {
+                messages.sort_by_key(|m| m.rumor.created_at);
+
+                let last: nostr::Timestamp = messages
+                    .last()
+                    .map(|m| m.rumor.created_at)
+                    .unwrap_or_default();
+                let subject: Option<String> = messages
+                    .iter()
+                    .rev()
+                    .find_map(|m| m.subject())
+                    .map(String::from);
+
+                let conversation: Conversation = Conversation {
+                    participants: participants
+                        .into_iter()
+                        .map(|p| Arc::new(p.into()))
+                        .collect(),
+                    subject,
+                    messages: messages.into_iter().map(Into::into).collect(),
+                };
+
+                (last, conversation)
+            })
+            .collect();
+
+        conversations.sort_by(|a, b| b.0.cmp(&a.0));
+        conversations.into_iter().map(|(.., c)| c).collect()
+    }
+}
+
+#[derive(Object)]
+pub struct PrivateMessageStream {
+    stream: Mutex<Pin<Box<dyn Stream<Item = PrivateMessage> + Send>>>,
+}
+
+#[uniffi::export(async_runtime = "tokio")]
+impl PrivateMessageStream {
+    /// Consumes the next item from the stream.
+    ///
+    /// Returns null if the stream is terminated.
+    pub async fn next(&self) -> Option<PrivateMessage> {
+        let mut stream = self.stream.lock().await;
+        stream.next().await
+    }
+}
@@ -7,25 +7,51 @@ use std::ops::Deref;
 use std::sync::Arc;
 use std::time::Duration;
 
+use futures_util::StreamExt;
+use futures_util::future::AbortHandle;
 use nostr::SubscriptionId;
 use nostr_sdk::client;
+use tokio::sync::Mutex;
 use uniffi::Object;
 
 mod api;
 mod builder;
+mod count;
+mod gap_fill;
+#[cfg(feature = "nip59")]
+mod inbox;
 mod notification;
+mod outbox;
 mod output;
+mod paginator;
+mod pow_retry;
 mod req_target;
+#[cfg(feature = "nip59")]
+mod rumors;
+mod store;
 mod stream;
-
+mod subscriptions;
+
+use self::count::GossipSelection;
+#[cfg(feature = "nip59")]
+pub use self::inbox::{Conversation, PrivateMessage, PrivateMessageInbox, PrivateMessageStream};
+use self::notification::HandleNotification;
+use self::outbox::Outbox;
+pub use self::outbox::{OutboxItem, OutboxNotification, OutboxNotificationStream};
 use self::output::{ClientSyncSummaryOutput, Output, SubscribeOutput};
+pub use self::pow_retry::PowRetryPolicy;
 use self::req_target::ReqTarget;
+#[cfg(feature = "nip59")]
+pub(crate) use self::rumors::RumorCache;
 use self::stream::{ClientEventStream, ClientNotificationStream};
+use crate::authenticator::SentAuthEvents;
 use crate::database::NostrDatabase;
 use crate::database::events::Events;
-use crate::error::Result;
+use crate::error::{NostrSdkError, Result};
 use crate::monitor::Monitor;
 use crate::protocol::filter::Filter;
+use crate::protocol::message::{MachineReadablePrefix, RelayMessage};
+use crate::protocol::signer::AsyncNostrSigner;
 use crate::protocol::types::RelayUrl;
 use crate::relay::capabilities::RelayCapabilities;
 use crate::relay::options::{SubscribeAutoCloseOptions, SyncOptions};
@@ -34,11 +60,33 @@ use crate::relay::{Relay, RelayOptions, ReqExitPolicy};
 #[derive(Object)]
 pub struct Client {
     inner: client::Client,
+    /// Authentication events sent by the authenticator, matched by the monitor
+    auth_events: SentAuthEvents,
+    persist_subscriptions: bool,
+    local_store_secret: Option<nostr::SecretKey>,
+    signer: Option<Arc<dyn AsyncNostrSigner>>,
+    rumor_cache_key: Option<nostr::SecretKey>,
+    pow_retry: Option<Arc<PowRetryPolicy>>,
+    outbox: Option<Arc<Outbox>>,
+    gossip: Option<GossipSelection>,
+    /// Gap filling tasks, by subscription ID
+    gap_fillers: Mutex<HashMap<SubscriptionId, AbortHandle>>,
 }
 
 impl From<client::Client> for Client {
     fn from(inner: client::Client) -> Self {
-        Self { inner }
+        Self {
+            inner,
+            auth_events: SentAuthEvents::default(),
+            persist_subscriptions: false,
+            local_store_secret: None,
+            signer: None,
+            rumor_cache_key: None,
+            pow_retry: None,
+            outbox: None,
+            gossip: None,
+            gap_fillers: Mutex::new(HashMap::new()),
+        }
     }
 }
 
@@ -51,6 +99,15 @@ impl Client {
     pub fn new() -> Self {
         Self {
             inner: client::Client::default(),
+            auth_events: SentAuthEvents::default(),
+            persist_subscriptions: false,
+            local_store_secret: None,
+            signer: None,
+            rumor_cache_key: None,
+            pow_retry: None,
+            outbox: None,
+            gossip: None,
+            gap_fillers: Mutex::new(HashMap::new()),
         }
     }
 
@@ -60,7 +117,13 @@ impl Client {
 
     /// Get monitor
     pub fn monitor(&self) -> Option<Arc<Monitor>> {
-        self.inner.monitor().cloned().map(|m| Arc::new(m.into()))
+        self.inner.monitor().cloned().map(|m| {
+            Arc::new(Monitor::with_client(
+                m,
+                self.inner.clone(),
+                self.auth_events.clone(),
+            ))
+        })
     }
 
     pub async fn shutdown(&self) {
@@ -77,6 +140,47 @@ impl Client {
         self.inner.notifications().into()
     }
 
+    /// Handle notifications
+    ///
+    /// Returns when the handler asks to stop, when it returns an error or when the client shutdowns.
+    ///
+    /// <div class="warning">When you call this method, you subscribe to the notifications channel from that precise moment. Anything received by relay/s before that moment is not included in the channel!</div>
+    pub async fn handle_notifications(&self, handler: Arc<dyn HandleNotification>) -> Result<()> {
+        let mut notifications = self.inner.notifications();
+
+        while let Some(notification) = notifications.next().await {
+            let stop: bool = match notification {
+                client::ClientNotification::Event {
+                    relay_url,
+                    subscription_id,
+                    event,
+                } => {
+                    handler
+                        .handle_event(
+                            Arc::new(relay_url.into()),
+                            subscription_id.to_string(),
+                            Arc::new((*event).into()),
+                        )
+                        .await?
+                }
+                client::ClientNotification::Message { relay_url, message } => {
+                    let message: RelayMessage = (*message).into();
+                    let prefix: Option<MachineReadablePrefix> = message.machine_readable_prefix();
+                    handler
+                        .handle_msg(Arc::new(relay_url.into()), Arc::new(message), prefix)
+                        .await?
+                }
+                client::ClientNotification::Shutdown => break,
+            };
+
+            if stop {
+                break;
+            }
+        }
+
+        Ok(())
+    }
+
     /// Get relays
     ///
     /// By default, gets only relays with `READ` or `WRITE` flags.
@@ -343,36 +447,105 @@ impl Client {
     /// - The resolved target contains no relays,
     /// - A specified relay does not exist in the pool,
     /// - Target resolution fails.
-    #[uniffi::method(default(id = None, close_on = None))]
+    ///
+    /// # Persistence
+    ///
+    /// If [`ClientBuilder::persist_subscriptions`] is enabled, the subscriptions without
+    /// auto-close conditions are saved into the database and can be restored with [`Client::restore_subscriptions`].
+    ///
+    /// # Gap filling
+    ///
+    /// If `gap_fill` is `true`, when a relay reconnects the `REQ` is re-sent to it with `since`
+    /// set to the newest event received from that relay, so the events published during the outage aren't lost.
+    /// Requires a monitor ([`ClientBuilder::monitor`]).
+    #[uniffi::method(default(id = None, close_on = None, gap_fill = false))]
     pub async fn subscribe(
         &self,
         target: &ReqTarget,
         id: Option<String>,
         close_on: Option<Arc<SubscribeAutoCloseOptions>>,
+        gap_fill: bool,
     ) -> Result<SubscribeOutput> {
+        if gap_fill && self.inner.monitor().is_none() {
+            return Err(NostrSdkError::Relay {
+                msg: String::from(
+                    "Gap filling requires a monitor: set it with ClientBuilder::monitor",
+                ),
+            });
+        }
+
         let mut builder = self.inner.subscribe(target.deref().clone());
 
-        if let Some(id) = id {
-            builder = builder.with_id(SubscriptionId::new(id));
+        // The gap filler needs the ID before sending the `REQ`
+        let id: Option<SubscriptionId> = match id {
+            Some(id) => Some(SubscriptionId::new(id)),
+            None if gap_fill => Some(SubscriptionId::generate()),
+            None => None,
+        };
+
+        if let Some(id) = &id {
+            builder = builder.with_id(id.clone());
         }
 
+        let persist: bool = self.persist_subscriptions && close_on.is_none();
+
         if let Some(close_on) = close_on {
             builder = builder.close_on(**close_on);
         }
 
-        Ok(builder.await?.into())
+        let gap_fill_id: Option<SubscriptionId> = if gap_fill { id } else { None };
+
+        if let Some(id) = &gap_fill_id {
+            self.start_gap_filler(id.clone()).await?;
+        }
+
+        let output = match builder.await {
+            Ok(output) => output,
+            Err(e) => {
+                if let Some(id) = &gap_fill_id {
+                    self.stop_gap_filler(id).await;
+                }
+                return Err(e.into());
+            }
+        };
+
+        if persist {
+            // Don't leave a live subscription that isn't tracked
+            if let Err(e) = self.save_subscription(&output.value).await {
+                if let Some(id) = &gap_fill_id {
+                    self.stop_gap_filler(id).await;
+                }
+                self.inner.unsubscribe(&output.value).await?;
+                return Err(e);
+            }
+        }
+
+        Ok(output.into())
     }
 
     pub async fn unsubscribe(&self, subscription_id: String) -> Result<Output> {
-        Ok(self
-            .inner
-            .unsubscribe(&SubscriptionId::new(subscription_id))
-            .await?
-            .into())
+        let id: SubscriptionId = SubscriptionId::new(subscription_id);
+        let output = self.inner.unsubscribe(&id).await?;
+
+        self.stop_gap_filler(&id).await;
+
+        if self.persist_subscriptions {
+            self.remove_subscription(&id).await?;
+        }
+
+        Ok(output.into())
     }
 
     pub async fn unsubscribe_all(&self) -> Result<Output> {
-        Ok(self.inner.unsubscribe_all().await?.into())
+        let output = self.inner.unsubscribe_all().await?;
+
+        self.stop_all_gap_fillers().await;
+
+        if self.persist_subscriptions {
+            self.remove_all_subscriptions().await?;
+        }
+
+        Ok(output.into())
     }
 
     /// Synchronize events with relays using negentropy.
@@ -3,8 +3,9 @@ use std::sync::Arc;
 use nostr_sdk::client;
 use uniffi::Enum;
 
+use crate::error::Result;
 use crate::protocol::event::Event;
-use crate::protocol::message::RelayMessage;
+use crate::protocol::message::{MachineReadablePrefix, RelayMessage};
 use crate::protocol::types::RelayUrl;
 
 /// Nostr client notification
@@ -38,6 +39,8 @@ pub enum ClientNotification {
         relay_url: Arc<RelayUrl>,
         /// The received relay message.
         message: Arc<RelayMessage>,
+        /// Machine-readable prefix of `OK` and `CLOSED` messages
+        prefix: Option<MachineReadablePrefix>,
     },
     /// Shutdown
     ///
@@ -57,11 +60,41 @@ impl From<client::ClientNotification> for ClientNotification {
                 subscription_id: subscription_id.to_string(),
                 event: Arc::new((*event).into()),
             },
-            client::ClientNotification::Message { relay_url, message } => Self::Message {
-                relay_url: Arc::new(relay_url.into()),
-                message: Arc::new((*message).into()),
-            },
+            client::ClientNotification::Message { relay_url, message } => {
+                let message: RelayMessage = (*message).into();
+                Self::Message {
+                    relay_url: Arc::new(relay_url.into()),
+                    prefix: message.machine_readable_prefix(),
+                    message: Arc::new(message),
+                }
+            }
             client::ClientNotification::Shutdown => Self::Shutdown,
         }
     }
 }
+
+/// Handle client notifications
+#[uniffi::export(with_foreign)]
+#[async_trait::async_trait]
+pub trait HandleNotification: Send + Sync {
+    /// Handle a new event
+    ///
+    /// Called only the **first time** the event is seen. Return `true` to stop handling notifications.
+    async fn handle_event(
+        &self,
+        relay_url: Arc<RelayUrl>,
+        subscription_id: String,
+        event: Arc<Event>,
+    ) -> Result<bool>;
+
+    /// Handle a relay message
+    ///
+    /// The `prefix` is the machine-readable prefix of `OK` and `CLOSED` messages.
+    /// Return `true` to stop handling notifications.
+    async fn handle_msg(
+        &self,
+        relay_url: Arc<RelayUrl>,
+        msg: Arc<RelayMessage>,
+        prefix: Option<MachineReadablePrefix>,
+    ) -> Result<bool>;
+}
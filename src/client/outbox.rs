@@ -0,0 +1,463 @@
+// Copyright (c) 2023-2025 Rust Nostr Developers
+// Distributed under the MIT software license
+
+//! Persistent outbox
+//!
+//! Retain the events that failed to be sent and retry them, with backoff, when the relays are connected.
+
+use std::ops::Deref;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+
+use async_utility::{task, time};
+use futures_util::{StreamExt, future};
+use nostr::serde_json::{self, Value, json};
+use nostr_sdk::{client, relay};
+use tokio::sync::Mutex;
+use tokio::sync::broadcast::error::RecvError;
+use tokio::sync::broadcast::{self, Receiver, Sender};
+use uniffi::{Enum, Object, Record};
+
+use super::Client;
+use super::output::SendEventOutput;
+use super::store::LocalStore;
+use crate::error::{NostrSdkError, Result};
+use crate::protocol::event::{Event, EventId};
+use crate::protocol::message::MachineReadablePrefix;
+use crate::protocol::types::RelayUrl;
+
+/// Records namespace
+const NAMESPACE: &str = "outbox";
+/// Interval between the outbox checks
+const POLL_INTERVAL: Duration = Duration::from_secs(5);
+/// Initial retry delay, doubled at every attempt
+const INITIAL_BACKOFF: Duration = Duration::from_secs(5);
+/// Max retry delay
+const MAX_BACKOFF: Duration = Duration::from_secs(60 * 10);
+/// Max attempts before dropping an event
+const MAX_ATTEMPTS: u32 = 10;
+/// Max attempts for the relays that fail without a machine-readable prefix
+const MAX_UNPREFIXED_ATTEMPTS: u32 = 3;
+
+/// Outbox notification
+#[derive(Clone, Enum)]
+pub enum OutboxNotification {
+    /// Event delivered to a relay
+    Delivered {
+        event_id: Arc<EventId>,
+        relay_url: Arc<RelayUrl>,
+    },
+    /// Event failed to be delivered to a relay: will be retried
+    Failed {
+        event_id: Arc<EventId>,
+        relay_url: Arc<RelayUrl>,
+        error: String,
+    },
+    /// Event dropped without being delivered to the relays
+    ///
+    /// After too many attempts or if the relays rejected it with a permanent error (i.e., `blocked:`, `invalid:`).
+    Dropped {
+        event_id: Arc<EventId>,
+        relays: Vec<Arc<RelayUrl>>,
+    },
+}
+
+/// Outbox item
+#[derive(Record)]
+pub struct OutboxItem {
+    /// Pending event
+    pub event: Arc<Event>,
+    /// Relays to which the event hasn't been delivered yet
+    pub relays: Vec<Arc<RelayUrl>>,
+    /// Number of retry attempts
+    pub attempts: u32,
+}
+
+pub(crate) struct Outbox {
+    notifications: Sender<OutboxNotification>,
+    running: AtomicBool,
+}
+
+impl Outbox {
+    pub fn new() -> Self {
+        let (notifications, ..) = broadcast::channel(1024);
+        Self {
+            notifications,
+            running: AtomicBool::new(false),
+        }
+    }
+
+    fn notify(&self, notification: OutboxNotification) {
+        let _ = self.notifications.send(notification);
+    }
+}
+
+#[derive(Debug)]
+struct OutboxRecord {
+    event: nostr::Event,
+    relays: Vec<nostr::RelayUrl>,
+    attempts: u32,
+    next_retry: nostr::Timestamp,
+}
+
+impl OutboxRecord {
+    fn parse(content: &str) -> Result<Self> {
+        let value: Value = serde_json::from_str(content)?;
+
+        let event: nostr::Event =
+            serde_json::from_value(value.get("event").cloned().ok_or_else(|| {
+                NostrSdkError::Database {
+                    msg: String::from("Invalid outbox record"),
+                }
+            })?)?;
+
+        let mut relays: Vec<nostr::RelayUrl> = Vec::new();
+
+        for url in value
+            .get("relays")
+            .and_then(Value::as_array)
+            .into_iter()
+            .flatten()
+            .filter_map(Value::as_str)
+        {
+            relays.push(nostr::RelayUrl::parse(url)?);
+        }
+
+        let attempts: u32 = value
+            .get("attempts")
+            .and_then(Value::as_u64)
+            .and_then(|a| u32::try_from(a).ok())
+            .unwrap_or_default();
+        let next_retry: nostr::Timestamp = nostr::Timestamp::from_secs(
+            value
+                .get("next_retry")
+                .and_then(Value::as_u64)
+                .unwrap_or_default(),
+        );
+
+        Ok(Self {
+            event,
+            relays,
+            attempts,
+            next_retry,
+        })
+    }
+
+    async fn save(&self, store: &LocalStore) -> Result<()> {
+        let relays: Vec<String> = self.relays.iter().map(|u| u.to_string()).collect();
+        let content: String = json!({
+            "event": serde_json::to_value(&self.event)?,
+            "relays": relays,
+            "attempts": self.attempts,
+            "next_retry": self.next_retry.as_u64(),
+        })
+        .to_string();
+
+        store
+            .save(NAMESPACE, &self.event.id.to_hex(), content)
+            .await
+    }
+
+    /// Delay before the next attempt
+    fn backoff(&self) -> Duration {
+        let factor: u32 = 2u32.saturating_pow(self.attempts.saturating_sub(1));
+        INITIAL_BACKOFF.saturating_mul(factor).min(MAX_BACKOFF)
+    }
+}
+
+/// Check if the relay error is worth a retry
+fn is_transient(prefix: Option<&MachineReadablePrefix>) -> bool {
+    matches!(
+        prefix,
+        None | Some(
+            MachineReadablePrefix::RateLimited
+                | MachineReadablePrefix::AuthRequired
+                | MachineReadablePrefix::Error
+        )
+    )
+}
+
+/// Retry the due records
+///
+/// Returns `true` if some records are still pending.
+async fn process(inner: &client::Client, store: &LocalStore, outbox: &Outbox) -> Result<bool> {
+    let records = store.list(NAMESPACE).await?;
+    let pending: bool = !records.is_empty();
+    let now: nostr::Timestamp = nostr::Timestamp::now();
+
+    for (id, content) in records.into_iter() {
+        let mut record: OutboxRecord = match OutboxRecord::parse(&content) {
+            Ok(record) => record,
+            Err(e) => {
+                tracing::warn!(%id, error = %e, "Removing invalid outbox record");
+                store.remove(NAMESPACE, &id).await?;
+                continue;
+            }
+        };
+
+        if record.next_retry > now {
+            continue;
+        }
+
+        let event_id = Arc::new(EventId::from(record.event.id));
+        let mut remaining: Vec<nostr::RelayUrl> = Vec::with_capacity(record.relays.len());
+        let mut attempted: bool = false;
+
+        for url in record.relays.into_iter() {
+            // Relay removed from the pool
+            let Some(r) = inner.relay(&url).await? else {
+                continue;
+            };
+
+            if !matches!(
+                r.status(),
+                relay::RelayStatus::Connected | relay::RelayStatus::Sleeping
+            ) {
+                remaining.push(url);
+                continue;
+            }
+
+            attempted = true;
+
+            let target: Vec<nostr::RelayUrl> = vec![url.clone()];
+
+            match inner.send_event(&record.event).to(&target).await {
+                Ok(output) if output.success.contains_key(&url) => {
+                    outbox.notify(OutboxNotification::Delivered {
+                        event_id: event_id.clone(),
+                        relay_url: Arc::new(url.into()),
+                    });
+                }
+                res => {
+                    let error: String = match res {
+                        Ok(mut output) => output.failed.remove(&url).unwrap_or_default(),
+                        Err(e) => e.to_string(),
+                    };
+                    let prefix: Option<MachineReadablePrefix> =
+                        MachineReadablePrefix::parse(&error);
+
+                    // Unknown errors may be permanent too: give up earlier
+                    let retry: bool = is_transient(prefix.as_ref())
+                        && (prefix.is_some() || record.attempts + 1 < MAX_UNPREFIXED_ATTEMPTS);
+
+                    if retry {
+                        outbox.notify(OutboxNotification::Failed {
+                            event_id: event_id.clone(),
+                            relay_url: Arc::new(url.clone().into()),
+                            error,
+                        });
+                        remaining.push(url);
+                    } else {
+                        tracing::warn!(%url, %error, "Dropping outbox event for relay");
+                        outbox.notify(OutboxNotification::Dropped {
+                            event_id: event_id.clone(),
+                            relays: vec![Arc::new(url.into())],
+                        });
+                    }
+                }
+            }
+        }
+
+        record.relays = remaining;
+
+        if record.relays.is_empty() {
+            store.remove(NAMESPACE, &record.event.id.to_hex()).await?;
+            continue;
+        }
+
+        if !attempted {
+            continue;
+        }
+
+        record.attempts += 1;
+
+        if record.attempts >= MAX_ATTEMPTS {
+            store.remove(NAMESPACE, &record.event.id.to_hex()).await?;
+            outbox.notify(OutboxNotification::Dropped {
+                event_id,
+                relays: record
+                    .relays
+                    .into_iter()
+                    .map(|u| Arc::new(u.into()))
+                    .collect(),
+            });
+            continue;
+        }
+
+        record.next_retry = now + record.backoff();
+        record.save(store).await?;
+    }
+
+    Ok(pending)
+}
+
+impl Client {
+    fn outbox(&self) -> Result<&Arc<Outbox>> {
+        self.outbox.as_ref().ok_or_else(|| NostrSdkError::Database {
+            msg: String::from("Outbox not enabled: set it with ClientBuilder::outbox"),
+        })
+    }
+
+    /// Queue the event for the relays that failed with a transient error
+    pub(crate) async fn enqueue_outbox(
+        &self,
+        event: &nostr::Event,
+        output: &SendEventOutput,
+    ) -> Result<()> {
+        let relays: Vec<nostr::RelayUrl> = output
+            .failed
+            .keys()
+            .filter(|url| is_transient(output.failed_prefixes.get(*url)))
+            .map(|url| url.as_ref().deref().clone())
+            .collect();
+
+        if relays.is_empty() {
+            return Ok(());
+        }
+
+        let record: OutboxRecord = OutboxRecord {
+            event: event.clone(),
+            relays,
+            attempts: 0,
+            next_retry: nostr::Timestamp::now(),
+        };
+        record.save(&self.store()?).await?;
+
+        self.spawn_outbox_worker()
+    }
+
+    /// Spawn the retry task, if not already running
+    ///
+    /// The task stops when the outbox is empty or the client shutdowns.
+    fn spawn_outbox_worker(&self) -> Result<()> {
+        let outbox: Arc<Outbox> = self.outbox()?.clone();
+        let store: LocalStore = self.store()?;
+
+        if outbox.running.swap(true, Ordering::SeqCst) {
+            return Ok(());
+        }
+
+        let inner: client::Client = self.inner.clone();
+        let mut notifications = inner.notifications();
+
+        let worker = {
+            let outbox: Arc<Outbox> = outbox.clone();
+            async move {
+                loop {
+                    match process(&inner, &store, &outbox).await {
+                        Ok(true) => {}
+                        Ok(false) => {
+                            outbox.running.store(false, Ordering::SeqCst);
+
+                            // An event may have been queued while the flag was still set
+                            match store.list(NAMESPACE).await {
+                                Ok(records) if !records.is_empty() => {}
+                                _ => break,
+                            }
+
+                            if outbox.running.swap(true, Ordering::SeqCst) {
+                                break;
+                            }
+                        }
+                        // Keep running: the records are retried at the next round
+                        Err(e) => tracing::error!(error = %e, "Outbox processing failed"),
+                    }
+
+                    time::sleep(POLL_INTERVAL).await;
+                }
+            }
+        };
+
+        let shutdown = async move {
+            while let Some(notification) = notifications.next().await {
+                if let client::ClientNotification::Shutdown = notification {
+                    break;
+                }
+            }
+        };
+
+        task::spawn(async move {
+            // The worker clears the flag by itself when it stops
+            if let future::Either::Right(..) =
+                future::select(Box::pin(worker), Box::pin(shutdown)).await
+            {
+                outbox.running.store(false, Ordering::SeqCst);
+            }
+        });
+
+        Ok(())
+    }
+}
+
+#[uniffi::export(async_runtime = "tokio")]
+impl Client {
+    /// Get the events waiting in the outbox
+    ///
+    /// Requires [`ClientBuilder::outbox`].
+    pub async fn pending_outbox(&self) -> Result<Vec<OutboxItem>> {
+        self.outbox()?;
+
+        let mut items: Vec<OutboxItem> = Vec::new();
+
+        for (id, content) in self.store()?.list(NAMESPACE).await? {
+            let record: OutboxRecord = match OutboxRecord::parse(&content) {
+                Ok(record) => record,
+                Err(e) => {
+                    tracing::warn!(%id, error = %e, "Skipping invalid outbox record");
+                    continue;
+                }
+            };
+            items.push(OutboxItem {
+                event: Arc::new(record.event.into()),
+                relays: record
+                    .relays
+                    .into_iter()
+                    .map(|u| Arc::new(u.into()))
+                    .collect(),
+                attempts: record.attempts,
+            });
+        }
+
+        Ok(items)
+    }
+
+    /// Start retrying the events in the outbox
+    ///
+    /// The retry task starts automatically when an event is queued:
+    /// call this method after a restart to resume the delivery of the persisted events.
+    pub async fn retry_outbox(&self) -> Result<()> {
+        self.spawn_outbox_worker()
+    }
+
+    /// Get a stream of outbox delivery notifications
+    pub fn outbox_notifications(&self) -> Result<OutboxNotificationStream> {
+        let outbox: &Arc<Outbox> = self.outbox()?;
+        Ok(OutboxNotificationStream {
+            receiver: Mutex::new(outbox.notifications.subscribe()),
+        })
+    }
+}
+
+#[derive(Object)]
+pub struct OutboxNotificationStream {
+    receiver: Mutex<Receiver<OutboxNotification>>,
+}
+
+#[uniffi::export(async_runtime = "tokio")]
+impl OutboxNotificationStream {
+    /// Consumes the next item from the stream.
+    ///
+    /// Returns null if the stream is terminated.
+    pub async fn next(&self) -> Option<OutboxNotification> {
+        let mut receiver = self.receiver.lock().await;
+
+        loop {
+            match receiver.recv().await {
+                Ok(notification) => return Some(notification),
+                Err(RecvError::Lagged(..)) => continue,
+                Err(RecvError::Closed) => return None,
+            }
+        }
+    }
+}
@@ -9,7 +9,8 @@ use nostr::SubscriptionId;
 use nostr_sdk::client;
 use uniffi::Record;
 
-use crate::protocol::event::EventId;
+use crate::protocol::event::{Event, EventId};
+use crate::protocol::message::MachineReadablePrefix;
 use crate::protocol::types::RelayUrl;
 
 /// Output
@@ -34,20 +35,38 @@ impl From<client::Output<()>> for Output {
 #[derive(Record)]
 pub struct SendEventOutput {
     /// Event ID
+    ///
+    /// The ID of the original event, also if the relays accepted the mined one (see `mined_event`).
     pub id: Arc<EventId>,
     /// Set of relays that success
     pub success: Vec<Arc<RelayUrl>>,
     /// Map of relays that failed, with related errors.
     pub failed: HashMap<Arc<RelayUrl>, String>,
+    /// Machine-readable prefixes of the failed relays errors
+    ///
+    /// Only the relays that replied with a known prefix are included.
+    pub failed_prefixes: HashMap<Arc<RelayUrl>, MachineReadablePrefix>,
+    /// Event mined and re-sent to the relays that required PoW
+    ///
+    /// The relays in `success` may have accepted the original event or this one.
+    /// Set only if a [`PowRetryPolicy`](crate::client::PowRetryPolicy) is configured and a retry was done.
+    pub mined_event: Option<Arc<Event>>,
 }
 
 impl From<client::SendEventOutput> for SendEventOutput {
     fn from(output: client::SendEventOutput) -> Self {
         let out = convert_output(output.success, output.failed);
+        let failed_prefixes = out
+            .failed
+            .iter()
+            .filter_map(|(url, e)| Some((url.clone(), MachineReadablePrefix::parse(e)?)))
+            .collect();
         Self {
             id: Arc::new(output.value.into()),
             success: out.success,
             failed: out.failed,
+            failed_prefixes,
+            mined_event: None,
         }
     }
 }
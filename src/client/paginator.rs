@@ -0,0 +1,384 @@
+// Copyright (c) 2023-2025 Rust Nostr Developers
+// Distributed under the MIT software license
+
+use std::collections::{HashMap, HashSet};
+use std::ops::Deref;
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_utility::time;
+use futures_util::StreamExt;
+use nostr::serde_json::{self, Map, Value, json};
+use nostr_sdk::{client, prelude, relay};
+use tokio::sync::Mutex;
+use uniffi::Object;
+
+use super::Client;
+use crate::error::{NostrSdkError, Result};
+use crate::protocol::event::Event;
+use crate::protocol::filter::Filter;
+use crate::protocol::types::RelayUrl;
+
+/// Pagination cursor of a relay
+#[derive(Debug, Clone, Copy)]
+enum RelayCursor {
+    /// Next page starts at `until` (`None` for the first page)
+    Until {
+        until: Option<nostr::Timestamp>,
+        /// Max number of events per page returned by the relay, if lower than the page size
+        cap: Option<usize>,
+    },
+    /// No more events
+    Exhausted,
+}
+
+/// Page received from a relay
+#[derive(Debug, Default)]
+struct RelayPage {
+    count: usize,
+    oldest: Option<nostr::Timestamp>,
+    /// `EOSE` received
+    completed: bool,
+}
+
+#[derive(Debug)]
+struct PaginatorState {
+    cursors: HashMap<nostr::RelayUrl, RelayCursor>,
+    seen: HashSet<nostr::EventId>,
+    fetched: u64,
+}
+
+/// Events paginator
+///
+/// Walk backwards in time with `until` and `limit`, per relay.
+#[derive(Object)]
+pub struct EventPaginator {
+    client: client::Client,
+    filter: nostr::Filter,
+    page_size: usize,
+    max_events: Option<u64>,
+    timeout: Duration,
+    state: Mutex<PaginatorState>,
+}
+
+#[uniffi::export(async_runtime = "tokio")]
+impl EventPaginator {
+    /// Fetch the next page
+    ///
+    /// Events are deduplicated across relays and sorted from newest to oldest.
+    /// A relay is considered exhausted when it returns less events than the page size
+    /// (or than its own `limit` cap, if lower) before `EOSE`.
+    /// On timeout or error, the cursor of the relay is kept and the page is requested again at the next call.
+    ///
+    /// Returns an empty list when all relays are exhausted (or failing) or the max number of events has been reached.
+    pub async fn next_page(&self) -> Result<Vec<Arc<Event>>> {
+        let mut state = self.state.lock().await;
+
+        if self.max_events.is_some_and(|max| state.fetched >= max) {
+            return Ok(Vec::new());
+        }
+
+        let targets: HashMap<nostr::RelayUrl, Vec<nostr::Filter>> = state
+            .cursors
+            .iter()
+            .filter_map(|(url, cursor)| match cursor {
+                RelayCursor::Until { until, .. } => {
+                    let mut filter: nostr::Filter = self.filter.clone().limit(self.page_size);
+
+                    if let Some(until) = until {
+                        filter = filter.until(*until);
+                    }
+
+                    Some((url.clone(), vec![filter]))
+                }
+                RelayCursor::Exhausted => None,
+            })
+            .collect();
+
+        if targets.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let id: nostr::SubscriptionId = nostr::SubscriptionId::generate();
+
+        // Get the notifications before subscribing, to not miss any message
+        let mut notifications = self.client.notifications();
+
+        self.client
+            .subscribe(client::ReqTarget::manual(targets.clone()))
+            .with_id(id.clone())
+            .close_on(
+                relay::SubscribeAutoCloseOptions::default()
+                    .exit_policy(prelude::ReqExitPolicy::ExitOnEOSE)
+                    .timeout(Some(self.timeout)),
+            )
+            .await?;
+
+        let mut received: HashMap<nostr::RelayUrl, RelayPage> = HashMap::new();
+        let mut page: Vec<nostr::Event> = Vec::new();
+
+        let collect = async {
+            let mut pending: HashSet<&nostr::RelayUrl> = targets.keys().collect();
+
+            while let Some(notification) = notifications.next().await {
+                let (relay_url, message) = match notification {
+                    client::ClientNotification::Message {
+                        relay_url, message, ..
+                    } => (relay_url, message),
+                    client::ClientNotification::Shutdown => break,
+                    _ => continue,
+                };
+
+                match message.as_ref() {
+                    nostr::RelayMessage::Event {
+                        subscription_id,
+                        event,
+                    } => {
+                        if subscription_id.as_ref() != &id || event.verify().is_err() {
+                            continue;
+                        }
+
+                        let relay_page: &mut RelayPage = received.entry(relay_url).or_default();
+                        relay_page.count += 1;
+
+                        if relay_page
+                            .oldest
+                            .is_none_or(|oldest| event.created_at < oldest)
+                        {
+                            relay_page.oldest = Some(event.created_at);
+                        }
+
+                        if state.seen.insert(event.id) {
+                            page.push(nostr::Event::clone(event));
+                        }
+                    }
+                    nostr::RelayMessage::EndOfStoredEvents(subscription_id) => {
+                        if subscription_id.as_ref() != &id {
+                            continue;
+                        }
+
+                        received.entry(relay_url.clone()).or_default().completed = true;
+                        pending.remove(&relay_url);
+                    }
+                    nostr::RelayMessage::Closed {
+                        subscription_id, ..
+                    } => {
+                        if subscription_id.as_ref() != &id {
+                            continue;
+                        }
+
+                        pending.remove(&relay_url);
+                    }
+                    _ => continue,
+                }
+
+                if pending.is_empty() {
+                    break;
+                }
+            }
+        };
+
+        // On timeout, the relays without `EOSE` keep their cursor
+        let _ = time::timeout(Some(self.timeout), collect).await;
+        let _ = self.client.unsubscribe(&id).await;
+
+        // Update cursors
+        for url in targets.into_keys() {
+            let Some(RelayCursor::Until { until, cap }) = state.cursors.get(&url).copied() else {
+                continue;
+            };
+
+            let cursor: RelayCursor = match received.get(&url) {
+                Some(RelayPage {
+                    count,
+                    oldest: Some(oldest),
+                    completed: true,
+                }) => {
+                    // Less events than the page size: exhausted, or capped by the relay `limit`.
+                    // The first short page sets the cap: the relay is exhausted when it returns less events than it.
+                    let exhausted: bool = cap.is_some_and(|cap| *count < cap.min(self.page_size));
+                    let cap: Option<usize> = if *count < self.page_size {
+                        Some(cap.map_or(*count, |cap| cap.max(*count)))
+                    } else {
+                        cap
+                    };
+
+                    if exhausted {
+                        RelayCursor::Exhausted
+                    } else if until == Some(*oldest) {
+                        // Events with the same timestamp of the cursor are fetched again:
+                        // move back by one second if there is no progress
+                        RelayCursor::Until {
+                            until: Some(*oldest - 1),
+                            cap,
+                        }
+                    } else {
+                        RelayCursor::Until {
+                            until: Some(*oldest),
+                            cap,
+                        }
+                    }
+                }
+                // `EOSE` without events
+                Some(RelayPage {
+                    completed: true, ..
+                }) => RelayCursor::Exhausted,
+                // Timeout or error: keep the cursor
+                _ => RelayCursor::Until { until, cap },
+            };
+
+            state.cursors.insert(url, cursor);
+        }
+
+        page.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+
+        if let Some(max) = self.max_events {
+            let remaining: u64 = max.saturating_sub(state.fetched);
+            page.truncate(usize::try_from(remaining).unwrap_or(usize::MAX));
+        }
+
+        state.fetched = state.fetched.saturating_add(page.len() as u64);
+
+        Ok(page.into_iter().map(|e| Arc::new(e.into())).collect())
+    }
+
+    /// Check if all relays are exhausted or the max number of events has been reached
+    pub async fn is_exhausted(&self) -> bool {
+        let state = self.state.lock().await;
+
+        self.max_events.is_some_and(|max| state.fetched >= max)
+            || state
+                .cursors
+                .values()
+                .all(|c| matches!(c, RelayCursor::Exhausted))
+    }
+
+    /// Get the number of events fetched so far
+    pub async fn fetched(&self) -> u64 {
+        let state = self.state.lock().await;
+        state.fetched
+    }
+
+    /// Get the cursor, to resume the pagination later with [`Client::fetch_events_paginated`]
+    ///
+    /// The deduplication state is not included in the cursor.
+    pub async fn cursor(&self) -> Result<String> {
+        let state = self.state.lock().await;
+
+        let relays: Map<String, Value> = state
+            .cursors
+            .iter()
+            .map(|(url, cursor)| {
+                let value: Value = match cursor {
+                    RelayCursor::Until { until, cap } => {
+                        json!({ "until": until.map(|t| t.as_u64()), "cap": cap })
+                    }
+                    RelayCursor::Exhausted => json!({ "exhausted": true }),
+                };
+                (url.to_string(), value)
+            })
+            .collect();
+
+        Ok(json!({ "relays": relays, "fetched": state.fetched }).to_string())
+    }
+}
+
+fn parse_cursor(cursor: &str) -> Result<(HashMap<nostr::RelayUrl, RelayCursor>, u64)> {
+    let value: Value = serde_json::from_str(cursor)?;
+    let relays = value
+        .get("relays")
+        .and_then(Value::as_object)
+        .ok_or_else(|| NostrSdkError::Json {
+            msg: String::from("Invalid paginator cursor"),
+        })?;
+
+    let mut cursors: HashMap<nostr::RelayUrl, RelayCursor> = HashMap::with_capacity(relays.len());
+
+    for (url, cursor) in relays.iter() {
+        let url: nostr::RelayUrl = nostr::RelayUrl::parse(url)?;
+        let cursor: RelayCursor = if cursor.get("exhausted").and_then(Value::as_bool) == Some(true)
+        {
+            RelayCursor::Exhausted
+        } else {
+            RelayCursor::Until {
+                until: cursor
+                    .get("until")
+                    .and_then(Value::as_u64)
+                    .map(nostr::Timestamp::from_secs),
+                cap: cursor
+                    .get("cap")
+                    .and_then(Value::as_u64)
+                    .and_then(|cap| usize::try_from(cap).ok()),
+            }
+        };
+        cursors.insert(url, cursor);
+    }
+
+    let fetched: u64 = value
+        .get("fetched")
+        .and_then(Value::as_u64)
+        .unwrap_or_default();
+
+    Ok((cursors, fetched))
+}
+
+#[uniffi::export(async_runtime = "tokio")]
+impl Client {
+    /// Fetch events page by page, walking backwards in time
+    ///
+    /// Each relay is queried with its own `until` cursor and `limit` set to `page_size`.
+    /// If `relays` is empty, the `READ` and `WRITE` relays of the pool are used.
+    ///
+    /// The pagination stops when all relays are exhausted or `max_events` events have been returned.
+    /// Pass a `cursor`, obtained with [`EventPaginator::cursor`], to resume a previous pagination.
+    #[uniffi::method(default(relays = [], page_size = 100, max_events = None, timeout = None, cursor = None))]
+    pub async fn fetch_events_paginated(
+        &self,
+        filter: &Filter,
+        relays: Vec<Arc<RelayUrl>>,
+        page_size: u16,
+        max_events: Option<u64>,
+        timeout: Option<Duration>,
+        cursor: Option<String>,
+    ) -> Result<EventPaginator> {
+        let (cursors, fetched) = match cursor {
+            Some(cursor) => parse_cursor(&cursor)?,
+            None => {
+                let relays: Vec<nostr::RelayUrl> = if relays.is_empty() {
+                    self.inner.relays().await.into_keys().collect()
+                } else {
+                    relays
+                        .into_iter()
+                        .map(|u| u.as_ref().deref().clone())
+                        .collect()
+                };
+
+                let cursors = relays
+                    .into_iter()
+                    .map(|url| {
+                        let cursor = RelayCursor::Until {
+                            until: filter.until,
+                            cap: None,
+                        };
+                        (url, cursor)
+                    })
+                    .collect();
+
+                (cursors, 0)
+            }
+        };
+
+        Ok(EventPaginator {
+            client: self.inner.clone(),
+            filter: filter.deref().clone(),
+            page_size: usize::from(page_size.max(1)),
+            max_events,
+            timeout: timeout.unwrap_or(Duration::from_secs(10)),
+            state: Mutex::new(PaginatorState {
+                cursors,
+                seen: HashSet::new(),
+                fetched,
+            }),
+        })
+    }
+}
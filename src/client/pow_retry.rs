@@ -0,0 +1,195 @@
+// Copyright (c) 2023-2025 Rust Nostr Developers
+// Distributed under the MIT software license
+
+//! Proof of Work retry
+//!
+//! Mine and re-send the events rejected by relays with a `pow:` message (NIP-13).
+
+use std::num::NonZeroU8;
+use std::ops::Deref;
+use std::sync::Arc;
+use std::time::Duration;
+
+use nostr::event::AsyncSignEvent;
+use uniffi::Object;
+
+use super::Client;
+use super::output::SendEventOutput;
+use crate::error::{NostrSdkError, Result};
+use crate::protocol::message::MachineReadablePrefix;
+use crate::protocol::nips::nip13::{AsyncPowAdapter, IntermediateAsyncPowAdapter};
+use crate::protocol::signer::{AsyncNostrSigner, IntermediateAsyncNostrSigner};
+use crate::protocol::types::RelayUrl;
+
+/// Proof of Work retry policy
+///
+/// When a relay rejects an event with `pow: difficulty <n> required`,
+/// the event is mined, re-signed and sent again to the relays that rejected it.
+#[derive(Object)]
+pub struct PowRetryPolicy {
+    adapter: Arc<dyn AsyncPowAdapter>,
+    signer: Option<Arc<dyn AsyncNostrSigner>>,
+    max_difficulty: u8,
+}
+
+#[uniffi::export]
+impl PowRetryPolicy {
+    /// New PoW retry policy
+    ///
+    /// The `signer` must be the one of the event authors.
+    /// If not set, the client signer ([`ClientBuilder::signer`]) is used.
+    ///
+    /// Relays requiring a difficulty greater than `max_difficulty` are not retried.
+    #[uniffi::constructor(default(signer = None, max_difficulty = 32))]
+    pub fn new(
+        adapter: Arc<dyn AsyncPowAdapter>,
+        signer: Option<Arc<dyn AsyncNostrSigner>>,
+        max_difficulty: u8,
+    ) -> Self {
+        Self {
+            adapter,
+            signer,
+            max_difficulty,
+        }
+    }
+}
+
+/// Parse the difficulty required by a `pow:` message (i.e., `pow: difficulty 25 required`)
+fn required_difficulty(message: &str) -> Option<u8> {
+    let (.., rest) = message.split_once("pow:")?;
+    let digits: String = rest
+        .chars()
+        .skip_while(|c| !c.is_ascii_digit())
+        .take_while(char::is_ascii_digit)
+        .collect();
+    digits.parse().ok()
+}
+
+impl Client {
+    /// Mine the event and re-send it to the relays that rejected it with a `pow:` message
+    ///
+    /// The output is updated with the results of the retry.
+    /// If the retry fails (i.e., mining or signing error), the error is recorded in the `failed` map
+    /// of the retried relays: the relays that already accepted the original event are preserved.
+    pub(crate) async fn retry_with_pow(
+        &self,
+        policy: &PowRetryPolicy,
+        event: &nostr::Event,
+        mut output: SendEventOutput,
+        ok_timeout: Option<Duration>,
+        authentication_timeout: Option<Duration>,
+    ) -> SendEventOutput {
+        let mut relays: Vec<Arc<RelayUrl>> = Vec::new();
+        let mut difficulty: u8 = 0;
+
+        for (url, prefix) in output.failed_prefixes.iter() {
+            if *prefix != MachineReadablePrefix::Pow {
+                continue;
+            }
+
+            let Some(required) = output.failed.get(url).and_then(|e| required_difficulty(e)) else {
+                continue;
+            };
+
+            if required > policy.max_difficulty {
+                continue;
+            }
+
+            relays.push(url.clone());
+            difficulty = difficulty.max(required);
+        }
+
+        let Some(difficulty) = NonZeroU8::new(difficulty) else {
+            return output;
+        };
+
+        let (mined, retry) = match self
+            .mine_and_send(
+                policy,
+                event,
+                difficulty,
+                &relays,
+                ok_timeout,
+                authentication_timeout,
+            )
+            .await
+        {
+            Ok(res) => res,
+            Err(e) => {
+                for url in relays.into_iter() {
+                    output.failed.insert(url, format!("PoW retry failed: {e}"));
+                }
+                return output;
+            }
+        };
+
+        for url in retry.success.into_iter() {
+            output.failed.remove(&url);
+            output.failed_prefixes.remove(&url);
+            output.success.push(url);
+        }
+
+        for (url, error) in retry.failed.into_iter() {
+            output.failed.insert(url, error);
+        }
+
+        for (url, prefix) in retry.failed_prefixes.into_iter() {
+            output.failed_prefixes.insert(url, prefix);
+        }
+
+        output.mined_event = Some(Arc::new(mined.into()));
+
+        output
+    }
+
+    async fn mine_and_send(
+        &self,
+        policy: &PowRetryPolicy,
+        event: &nostr::Event,
+        difficulty: NonZeroU8,
+        relays: &[Arc<RelayUrl>],
+        ok_timeout: Option<Duration>,
+        authentication_timeout: Option<Duration>,
+    ) -> Result<(nostr::Event, SendEventOutput)> {
+        let unsigned: nostr::UnsignedEvent = nostr::UnsignedEvent::new(
+            event.pubkey,
+            event.created_at,
+            event.kind,
+            event.tags.clone().to_vec(),
+            event.content.clone(),
+        );
+
+        let adapter = IntermediateAsyncPowAdapter::new(policy.adapter.clone());
+        let unsigned: nostr::UnsignedEvent = unsigned.mine_async(&adapter, difficulty).await?;
+
+        let signer: Arc<dyn AsyncNostrSigner> = match &policy.signer {
+            Some(signer) => signer.clone(),
+            None => self.signer()?,
+        };
+        let signer = IntermediateAsyncNostrSigner::new(signer);
+        let mined: nostr::Event = signer.sign_event_async(unsigned).await?;
+
+        if mined.pubkey != event.pubkey {
+            return Err(NostrSdkError::Signer {
+                msg: String::from("The PoW retry signer is not the event author"),
+            });
+        }
+
+        let urls: Vec<nostr::RelayUrl> = relays
+            .iter()
+            .map(|url| url.as_ref().deref().clone())
+            .collect();
+        let mut builder = self.inner.send_event(&mined).to(&urls);
+
+        if let Some(ok_timeout) = ok_timeout {
+            builder = builder.ok_timeout(ok_timeout);
+        }
+        if let Some(authentication_timeout) = authentication_timeout {
+            builder = builder.authentication_timeout(authentication_timeout);
+        }
+
+        let retry: SendEventOutput = builder.await?.into();
+
+        Ok((mined, retry))
+    }
+}
@@ -8,16 +8,34 @@ use uniffi::Object;
 use crate::protocol::filter::Filter;
 use crate::protocol::types::RelayUrl;
 
+/// Relays and filters of a request target
+pub(super) enum RelayTargets {
+    /// Filters to send to the automatically selected relays
+    Auto(Vec<nostr::Filter>),
+    /// Filters per relay
+    Manual(HashMap<nostr::RelayUrl, Vec<nostr::Filter>>),
+}
+
 /// Request target
 #[derive(Object)]
-pub struct ReqTarget(client::ReqTarget<'static>);
+pub struct ReqTarget {
+    inner: client::ReqTarget<'static>,
+    targets: RelayTargets,
+}
 
 impl Deref for ReqTarget {
     type Target = client::ReqTarget<'static>;
 
     #[inline]
     fn deref(&self) -> &Self::Target {
-        &self.0
+        &self.inner
+    }
+}
+
+impl ReqTarget {
+    #[inline]
+    pub(super) fn targets(&self) -> &RelayTargets {
+        &self.targets
     }
 }
 
@@ -29,32 +47,47 @@ impl ReqTarget {
     /// With gossip enabled, also queries relays discovered from public keys in filters.
     #[uniffi::constructor]
     pub fn auto(filters: Vec<Arc<Filter>>) -> Self {
-        Self(client::ReqTarget::auto(
-            filters.into_iter().map(|f| f.as_ref().deref().clone()),
-        ))
+        let filters: Vec<nostr::Filter> = filters
+            .into_iter()
+            .map(|f| f.as_ref().deref().clone())
+            .collect();
+        Self {
+            inner: client::ReqTarget::auto(filters.clone()),
+            targets: RelayTargets::Auto(filters),
+        }
     }
 
     /// Target a specific relay.
     #[uniffi::constructor]
     pub fn single(url: &RelayUrl, filters: Vec<Arc<Filter>>) -> Self {
-        Self(client::ReqTarget::single(
-            url.deref().clone(),
-            filters.into_iter().map(|f| f.as_ref().deref().clone()),
-        ))
+        let filters: Vec<nostr::Filter> = filters
+            .into_iter()
+            .map(|f| f.as_ref().deref().clone())
+            .collect();
+        Self {
+            inner: client::ReqTarget::single(url.deref().clone(), filters.clone()),
+            targets: RelayTargets::Manual(HashMap::from([(url.deref().clone(), filters)])),
+        }
     }
 
     /// Target specific relays with their own filters.
     #[uniffi::constructor]
     pub fn manual(targets: HashMap<Arc<RelayUrl>, Vec<Arc<Filter>>>) -> Self {
-        let targets = targets.into_iter().map(|(url, filters)| {
-            (
-                url.as_ref().deref().clone(),
-                filters
-                    .into_iter()
-                    .map(|f| f.as_ref().deref().clone())
-                    .collect::<Vec<_>>(),
-            )
-        });
-        Self(client::ReqTarget::manual(targets))
+        let targets: HashMap<nostr::RelayUrl, Vec<nostr::Filter>> = targets
+            .into_iter()
+            .map(|(url, filters)| {
+                (
+                    url.as_ref().deref().clone(),
+                    filters
+                        .into_iter()
+                        .map(|f| f.as_ref().deref().clone())
+                        .collect::<Vec<_>>(),
+                )
+            })
+            .collect();
+        Self {
+            inner: client::ReqTarget::manual(targets.clone()),
+            targets: RelayTargets::Manual(targets),
+        }
     }
 }
@@ -0,0 +1,165 @@
+// Copyright (c) 2023-2025 Rust Nostr Developers
+// Distributed under the MIT software license
+
+//! Unwrapped gift wraps cache
+//!
+//! Keep the rumors of the unwrapped gift wraps (NIP-59), so they are not decrypted again.
+//! The records are NIP-44 encrypted to a local key: the rumors (i.e., the private messages) are never stored in plain text.
+
+use std::ops::Deref;
+use std::sync::Arc;
+
+use nostr::nips::{nip44, nip59};
+use nostr::serde_json::{self, Value, json};
+
+use super::Client;
+use super::store::LocalStore;
+use crate::error::{NostrSdkError, Result};
+use crate::protocol::event::{Event, EventId};
+use crate::protocol::nips::nip59::UnwrappedGift;
+use crate::protocol::signer::{AsyncNostrSigner, IntermediateAsyncNostrSigner};
+
+/// Records namespace
+const NAMESPACE: &str = "rumor";
+
+#[derive(Debug, Clone)]
+pub(crate) struct RumorCache {
+    store: LocalStore,
+    keys: nostr::Keys,
+}
+
+impl RumorCache {
+    pub fn new(store: LocalStore, key: nostr::SecretKey) -> Self {
+        Self {
+            store,
+            keys: nostr::Keys::new(key),
+        }
+    }
+
+    fn encrypt(&self, content: String) -> Result<String> {
+        Ok(nip44::encrypt(
+            self.keys.secret_key(),
+            &self.keys.public_key(),
+            content,
+            nip44::Version::V2,
+        )?)
+    }
+
+    /// Decrypt the record
+    ///
+    /// Returns `None` if the record was encrypted with another key (authenticated by the NIP-44 MAC).
+    fn decrypt(&self, content: String) -> Option<String> {
+        nip44::decrypt(self.keys.secret_key(), &self.keys.public_key(), content).ok()
+    }
+
+    /// Get the cached unwrapped gift
+    pub async fn get(&self, gift_wrap_id: &nostr::EventId) -> Result<Option<nip59::UnwrappedGift>> {
+        let Some(content) = self.store.get(NAMESPACE, &gift_wrap_id.to_hex()).await? else {
+            return Ok(None);
+        };
+
+        let Some(content) = self.decrypt(content) else {
+            return Ok(None);
+        };
+
+        let value: Value = serde_json::from_str(&content)?;
+        let sender: &str =
+            value
+                .get("sender")
+                .and_then(Value::as_str)
+                .ok_or_else(|| NostrSdkError::Database {
+                    msg: String::from("Invalid rumor record"),
+                })?;
+        let rumor: nostr::UnsignedEvent =
+            serde_json::from_value(value.get("rumor").cloned().ok_or_else(|| {
+                NostrSdkError::Database {
+                    msg: String::from("Invalid rumor record"),
+                }
+            })?)?;
+
+        Ok(Some(nip59::UnwrappedGift {
+            sender: nostr::PublicKey::from_hex(sender)?,
+            rumor,
+        }))
+    }
+
+    /// Cache an unwrapped gift
+    pub async fn save(
+        &self,
+        gift_wrap_id: &nostr::EventId,
+        gift: &nip59::UnwrappedGift,
+    ) -> Result<()> {
+        let content: String = json!({
+            "sender": gift.sender.to_hex(),
+            "rumor": serde_json::to_value(&gift.rumor)?,
+        })
+        .to_string();
+
+        self.store
+            .save(NAMESPACE, &gift_wrap_id.to_hex(), self.encrypt(content)?)
+            .await
+    }
+
+    /// Get the cached unwrapped gift or unwrap and cache it
+    pub async fn unwrap(
+        &self,
+        signer: &IntermediateAsyncNostrSigner,
+        gift_wrap: &nostr::Event,
+    ) -> Result<nip59::UnwrappedGift> {
+        if let Some(gift) = self.get(&gift_wrap.id).await? {
+            return Ok(gift);
+        }
+
+        let gift: nip59::UnwrappedGift =
+            nip59::UnwrappedGift::from_gift_wrap_async(signer, gift_wrap).await?;
+        self.save(&gift_wrap.id, &gift).await?;
+
+        Ok(gift)
+    }
+}
+
+impl Client {
+    /// Get the rumor cache
+    ///
+    /// Returns `None` if the cache key isn't set ([`ClientBuilder::rumor_cache_key`]).
+    pub(crate) fn rumor_cache(&self) -> Result<Option<RumorCache>> {
+        match &self.rumor_cache_key {
+            Some(key) => Ok(Some(RumorCache::new(self.store()?, key.clone()))),
+            None => Ok(None),
+        }
+    }
+}
+
+#[uniffi::export(async_runtime = "tokio")]
+impl Client {
+    /// Unwrap a gift wrap with the client signer ([`ClientBuilder::signer`])
+    ///
+    /// Internally verify the `seal` event.
+    /// If [`ClientBuilder::rumor_cache_key`] is set, the rumor is looked up in the cache first and cached after unwrapping.
+    pub async fn unwrap_gift_wrap(&self, gift_wrap: &Event) -> Result<UnwrappedGift> {
+        let signer: Arc<dyn AsyncNostrSigner> = self.signer()?;
+        let signer = IntermediateAsyncNostrSigner::new(signer);
+
+        let gift: nip59::UnwrappedGift = match self.rumor_cache()? {
+            Some(cache) => cache.unwrap(&signer, gift_wrap.deref()).await?,
+            None => nip59::UnwrappedGift::from_gift_wrap_async(&signer, gift_wrap.deref()).await?,
+        };
+
+        Ok(gift.into())
+    }
+
+    /// Get the cached unwrapped gift wrap
+    ///
+    /// Returns `None` if the gift wrap isn't cached or if [`ClientBuilder::rumor_cache_key`] isn't set.
+    pub async fn unwrapped_gift(
+        &self,
+        gift_wrap_id: &EventId,
+    ) -> Result<Option<Arc<UnwrappedGift>>> {
+        let Some(cache) = self.rumor_cache()? else {
+            return Ok(None);
+        };
+
+        let gift = cache.get(gift_wrap_id.deref()).await?;
+        Ok(gift.map(|g| Arc::new(g.into())))
+    }
+}
@@ -0,0 +1,147 @@
+// Copyright (c) 2023-2025 Rust Nostr Developers
+// Distributed under the MIT software license
+
+//! Local records store
+//!
+//! Persist client state into the database, as addressable events of a private kind
+//! signed by a local key. Records are tagged as protected (NIP-70), so relays reject them if published.
+//!
+//! The local key is derived from a per-install secret, so the events received from relays
+//! can't be mistaken for local records. The content is encrypted to the local key (NIP-44).
+
+use std::sync::Arc;
+
+use nostr::hashes::sha256::Hash as Sha256Hash;
+use nostr::hashes::{Hash, HashEngine};
+use nostr::nips::nip44;
+use nostr_sdk::prelude::NostrDatabase;
+
+use crate::error::Result;
+
+/// Domain separator of the local records key
+const LOCAL_KEY_SEED: &[u8] = b"nostr-sdk-ffi:local-store";
+/// Prefix of the records identifier
+const PREFIX: &str = "nostr-sdk-ffi";
+/// Kind of the local records
+///
+/// Addressable kind not assigned by any NIP, so the records don't show up in the app queries (i.e., NIP-78).
+const RECORD_KIND: nostr::Kind = nostr::Kind::Custom(39_998);
+
+#[derive(Debug, Clone)]
+pub(crate) struct LocalStore {
+    database: Arc<dyn NostrDatabase>,
+    keys: nostr::Keys,
+}
+
+impl LocalStore {
+    /// New local store, with the key derived from the per-install `secret`
+    pub fn new(database: Arc<dyn NostrDatabase>, secret: &nostr::SecretKey) -> Result<Self> {
+        let mut engine = Sha256Hash::engine();
+        engine.input(LOCAL_KEY_SEED);
+        engine.input(secret.to_secret_hex().as_bytes());
+        let hash: Sha256Hash = Sha256Hash::from_engine(engine);
+        let secret_key: nostr::SecretKey = nostr::SecretKey::from_slice(hash.as_byte_array())?;
+
+        Ok(Self {
+            database,
+            keys: nostr::Keys::new(secret_key),
+        })
+    }
+
+    fn identifier(namespace: &str, id: &str) -> String {
+        format!("{PREFIX}:{namespace}:{id}")
+    }
+
+    fn filter(&self) -> nostr::Filter {
+        nostr::Filter::new()
+            .kind(RECORD_KIND)
+            .author(self.keys.public_key())
+    }
+
+    /// Check if the record has been written locally
+    fn is_local(&self, event: &nostr::Event) -> bool {
+        event.pubkey == self.keys.public_key()
+            && event.kind == RECORD_KIND
+            && event
+                .tags
+                .iter()
+                .any(|tag| matches!(tag.as_slice(), [name] if name == "-"))
+    }
+
+    /// Decrypt the content of a local record
+    fn decrypt(&self, event: &nostr::Event) -> Option<String> {
+        if !self.is_local(event) {
+            return None;
+        }
+
+        nip44::decrypt(
+            self.keys.secret_key(),
+            &self.keys.public_key(),
+            &event.content,
+        )
+        .ok()
+    }
+
+    /// Save a record, replacing the previous one with the same ID
+    pub async fn save(&self, namespace: &str, id: &str, content: String) -> Result<()> {
+        let identifier: String = Self::identifier(namespace, id);
+
+        // Remove the previous record first: a replacement in the same second could be ignored
+        self.database
+            .delete(self.filter().identifier(&identifier))
+            .await?;
+
+        let content: String = nip44::encrypt(
+            self.keys.secret_key(),
+            &self.keys.public_key(),
+            content,
+            nip44::Version::V2,
+        )?;
+        let event: nostr::Event = nostr::EventBuilder::new(RECORD_KIND, content)
+            .tags([nostr::Tag::identifier(identifier), nostr::Tag::protected()])
+            .finalize(&self.keys)?;
+        self.database.save_event(&event).await?;
+
+        Ok(())
+    }
+
+    /// Get a record
+    pub async fn get(&self, namespace: &str, id: &str) -> Result<Option<String>> {
+        let filter: nostr::Filter = self
+            .filter()
+            .identifier(Self::identifier(namespace, id))
+            .limit(1);
+        let events = self.database.query(filter).await?;
+        Ok(events.into_iter().find_map(|e| self.decrypt(&e)))
+    }
+
+    /// Get all the records of a namespace, as `(id, content)` pairs
+    pub async fn list(&self, namespace: &str) -> Result<Vec<(String, String)>> {
+        let prefix: String = Self::identifier(namespace, "");
+        let events = self.database.query(self.filter()).await?;
+
+        Ok(events
+            .into_iter()
+            .filter_map(|event| {
+                let id: &str = event.tags.identifier()?.strip_prefix(&prefix)?;
+                Some((id.to_string(), self.decrypt(&event)?))
+            })
+            .collect())
+    }
+
+    /// Remove a record
+    pub async fn remove(&self, namespace: &str, id: &str) -> Result<()> {
+        let filter: nostr::Filter = self.filter().identifier(Self::identifier(namespace, id));
+        self.database.delete(filter).await?;
+        Ok(())
+    }
+
+    /// Remove all the records of a namespace
+    pub async fn clear(&self, namespace: &str) -> Result<()> {
+        for (id, ..) in self.list(namespace).await? {
+            self.remove(namespace, &id).await?;
+        }
+
+        Ok(())
+    }
+}
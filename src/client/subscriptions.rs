@@ -0,0 +1,162 @@
+// Copyright (c) 2023-2025 Rust Nostr Developers
+// Distributed under the MIT software license
+
+//! Persistent subscription registry
+
+use std::collections::HashMap;
+
+use nostr::SubscriptionId;
+use nostr::serde_json::{self, Map, Value, json};
+use nostr_sdk::client;
+
+use super::Client;
+use super::store::LocalStore;
+use crate::error::{NostrSdkError, Result};
+
+/// Records namespace
+const NAMESPACE: &str = "subscription";
+/// Max number of stored events scanned to find the last one received from a relay
+const RESUME_SCAN_LIMIT: usize = 100;
+
+impl Client {
+    /// Get the local records store
+    ///
+    /// Fails if the per-install secret isn't set ([`ClientBuilder::local_store_secret`]).
+    pub(crate) fn store(&self) -> Result<LocalStore> {
+        let secret: &nostr::SecretKey =
+            self.local_store_secret
+                .as_ref()
+                .ok_or_else(|| NostrSdkError::Database {
+                    msg: String::from(
+                        "Local store secret not set: use `ClientBuilder::local_store_secret`",
+                    ),
+                })?;
+        LocalStore::new(self.inner.database().clone(), secret)
+    }
+
+    /// Save the relays and filters of a subscription
+    pub(super) async fn save_subscription(&self, id: &SubscriptionId) -> Result<()> {
+        let targets = self.inner.subscription(id).await;
+
+        if targets.is_empty() {
+            return Ok(());
+        }
+
+        let mut relays: Map<String, Value> = Map::with_capacity(targets.len());
+
+        for (url, filters) in targets.into_iter() {
+            relays.insert(url.to_string(), serde_json::to_value(filters)?);
+        }
+
+        let content: String = json!({ "relays": relays }).to_string();
+
+        self.store()?.save(NAMESPACE, id.as_str(), content).await
+    }
+
+    pub(super) async fn remove_subscription(&self, id: &SubscriptionId) -> Result<()> {
+        self.store()?.remove(NAMESPACE, id.as_str()).await
+    }
+
+    pub(super) async fn remove_all_subscriptions(&self) -> Result<()> {
+        self.store()?.clear(NAMESPACE).await
+    }
+
+    /// Advance `since` to the newest stored event matching the filter and seen on the relay
+    async fn resume_filter(
+        &self,
+        url: &nostr::RelayUrl,
+        filter: nostr::Filter,
+    ) -> Result<nostr::Filter> {
+        let database = self.inner.database();
+        let events = database
+            .query(filter.clone().limit(RESUME_SCAN_LIMIT))
+            .await?;
+
+        // Newest first
+        for event in events.into_iter() {
+            let seen: bool = database
+                .event_seen_on_relays(&event.id)
+                .await?
+                .is_some_and(|relays| relays.contains(url));
+
+            if !seen {
+                continue;
+            }
+
+            if filter.since.is_none_or(|since| event.created_at > since) {
+                return Ok(filter.since(event.created_at));
+            }
+
+            break;
+        }
+
+        Ok(filter)
+    }
+}
+
+#[uniffi::export(async_runtime = "tokio")]
+impl Client {
+    /// Restore the subscriptions persisted with [`ClientBuilder::persist_subscriptions`]
+    ///
+    /// Each subscription is re-issued with the same ID. For each relay, `since` is advanced to the newest
+    /// matching event in the database received from that relay, so only the missed events are downloaded.
+    ///
+    /// Relays must be added before calling this method: the ones not in the pool are skipped.
+    ///
+    /// Returns the IDs of the restored subscriptions.
+    pub async fn restore_subscriptions(&self) -> Result<Vec<String>> {
+        let store: LocalStore = self.store()?;
+        let mut restored: Vec<String> = Vec::new();
+
+        for (id, content) in store.list(NAMESPACE).await? {
+            let mut targets: HashMap<nostr::RelayUrl, Vec<nostr::Filter>> = HashMap::new();
+
+            for (url, filters) in parse_targets(&content)? {
+                if self.inner.relay(&url).await?.is_none() {
+                    continue;
+                }
+
+                let mut resumed: Vec<nostr::Filter> = Vec::with_capacity(filters.len());
+
+                for filter in filters.into_iter() {
+                    resumed.push(self.resume_filter(&url, filter).await?);
+                }
+
+                targets.insert(url, resumed);
+            }
+
+            if targets.is_empty() {
+                continue;
+            }
+
+            let id: SubscriptionId = SubscriptionId::new(id);
+
+            self.inner
+                .subscribe(client::ReqTarget::manual(targets))
+                .with_id(id.clone())
+                .await?;
+
+            // Update the record with the advanced `since`
+            self.save_subscription(&id).await?;
+
+            restored.push(id.to_string());
+        }
+
+        Ok(restored)
+    }
+}
+
+fn parse_targets(content: &str) -> Result<HashMap<nostr::RelayUrl, Vec<nostr::Filter>>> {
+    let record: Value = serde_json::from_str(content)?;
+    let mut targets: HashMap<nostr::RelayUrl, Vec<nostr::Filter>> = HashMap::new();
+
+    if let Some(relays) = record.get("relays").and_then(Value::as_object) {
+        for (url, filters) in relays.iter() {
+            let url: nostr::RelayUrl = nostr::RelayUrl::parse(url)?;
+            let filters: Vec<nostr::Filter> = serde_json::from_value(filters.clone())?;
+            targets.insert(url, filters);
+        }
+    }
+
+    Ok(targets)
+}
@@ -0,0 +1,129 @@
+// Copyright (c) 2022-2023 Yuki Kishimoto
+// Copyright (c) 2023-2025 Rust Nostr Developers
+// Distributed under the MIT software license
+
+use std::fmt;
+use std::ops::Deref;
+use std::sync::Arc;
+use std::time::Duration;
+
+use nostr_connect::client;
+use uniffi::Object;
+
+mod signer;
+
+use crate::error::Result;
+use crate::protocol::key::Keys;
+use crate::protocol::nips::nip46::NostrConnectUri;
+use crate::protocol::signer::{AsyncNostrSigner, export_async_nostr_signer};
+use crate::protocol::types::RelayUrl;
+use crate::relay::RelayOptions;
+
+/// Handle the `auth_url` challenges sent by the remote signer
+#[uniffi::export(with_foreign)]
+#[async_trait::async_trait]
+pub trait AuthUrlHandler: Send + Sync {
+    /// Called when the remote signer requires the user to open an URL to approve a request
+    /// (i.e., to login in a web app).
+    async fn on_auth_url(&self, auth_url: String) -> Result<()>;
+}
+
+pub(crate) struct FFI2RustAuthUrlHandler {
+    pub(crate) inner: Arc<dyn AuthUrlHandler>,
+}
+
+impl fmt::Debug for FFI2RustAuthUrlHandler {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FFI2RustAuthUrlHandler").finish()
+    }
+}
+
+#[derive(Object)]
+pub struct NostrConnect {
+    inner: client::NostrConnect,
+}
+
+impl Deref for NostrConnect {
+    type Target = client::NostrConnect;
+
+    fn deref(&self) -> &Self::Target {
+        &self.inner
+    }
+}
+
+impl From<client::NostrConnect> for NostrConnect {
+    fn from(inner: client::NostrConnect) -> Self {
+        Self { inner }
+    }
+}
+
+#[uniffi::export(async_runtime = "tokio")]
+impl NostrConnect {
+    /// Construct Nostr Connect client
+    ///
+    /// The `auth_url_handler` is called when the remote signer replies with an `auth_url` challenge.
+    #[uniffi::constructor(default(auth_url_handler = None))]
+    pub fn new(
+        uri: &NostrConnectUri,
+        app_keys: &Keys,
+        timeout: Duration,
+        opts: Option<Arc<RelayOptions>>,
+        auth_url_handler: Option<Arc<dyn AuthUrlHandler>>,
+    ) -> Result<Self> {
+        let mut inner = client::NostrConnect::new(
+            uri.deref().clone(),
+            app_keys.deref().clone(),
+            timeout,
+            opts.map(|k| k.as_ref().deref().clone()),
+        )?;
+
+        if let Some(handler) = auth_url_handler {
+            inner.auth_url_handler(FFI2RustAuthUrlHandler { inner: handler });
+        }
+
+        Ok(Self { inner })
+    }
+
+    /// Get signer relays
+    pub fn relays(&self) -> Vec<Arc<RelayUrl>> {
+        self.inner
+            .relays()
+            .iter()
+            .cloned()
+            .map(|u| Arc::new(u.into()))
+            .collect()
+    }
+
+    /// Get `bunker` URI
+    pub async fn bunker_uri(&self) -> Result<NostrConnectUri> {
+        Ok(self.inner.bunker_uri().await?.into())
+    }
+}
+
+export_async_nostr_signer!(NostrConnect, |signer| &signer.inner);
+
+mod inner {
+    use std::error::Error;
+
+    use nostr::Url;
+    use nostr::prelude::BoxedFuture;
+    use nostr_connect::client::AuthUrlHandler;
+
+    use super::FFI2RustAuthUrlHandler;
+    use crate::error::MiddleError;
+
+    impl AuthUrlHandler for FFI2RustAuthUrlHandler {
+        fn on_auth_url(
+            &self,
+            auth_url: Url,
+        ) -> BoxedFuture<Result<(), Box<dyn Error + Send + Sync>>> {
+            Box::pin(async move {
+                self.inner
+                    .on_auth_url(auth_url.to_string())
+                    .await
+                    .map_err(MiddleError::from)?;
+                Ok(())
+            })
+        }
+    }
+}
@@ -0,0 +1,160 @@
+// Copyright (c) 2022-2023 Yuki Kishimoto
+// Copyright (c) 2023-2025 Rust Nostr Developers
+// Distributed under the MIT software license
+
+use std::fmt;
+use std::ops::Deref;
+use std::sync::Arc;
+
+use nostr::nips::nip46;
+use nostr_connect::signer;
+use uniffi::{Object, Record};
+
+use crate::error::Result;
+use crate::protocol::key::{Keys, PublicKey};
+use crate::protocol::nips::nip46::{NostrConnectPermission, NostrConnectRequest, NostrConnectUri};
+use crate::protocol::types::RelayUrl;
+use crate::relay::RelayOptions;
+
+/// Nostr Connect keys
+#[derive(Record)]
+pub struct NostrConnectKeys {
+    /// Keys used to communicate with the clients
+    pub signer: Arc<Keys>,
+    /// Keys used to sign events and encrypt/decrypt messages
+    pub user: Arc<Keys>,
+}
+
+impl From<NostrConnectKeys> for signer::NostrConnectKeys {
+    fn from(keys: NostrConnectKeys) -> Self {
+        Self {
+            signer: keys.signer.as_ref().deref().clone(),
+            user: keys.user.as_ref().deref().clone(),
+        }
+    }
+}
+
+/// Nostr Connect signer actions
+#[uniffi::export(with_foreign)]
+pub trait NostrConnectSignerActions: Send + Sync {
+    /// Approve or deny a request
+    ///
+    /// Return `true` to approve it.
+    fn approve(&self, public_key: Arc<PublicKey>, req: NostrConnectRequest) -> bool;
+}
+
+struct FFI2RustNostrConnectSignerActions {
+    inner: Arc<dyn NostrConnectSignerActions>,
+    permissions: Vec<NostrConnectPermission>,
+}
+
+impl fmt::Debug for FFI2RustNostrConnectSignerActions {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FFI2RustNostrConnectSignerActions")
+            .field("permissions", &self.permissions)
+            .finish()
+    }
+}
+
+impl signer::NostrConnectSignerActions for FFI2RustNostrConnectSignerActions {
+    fn approve(&self, public_key: &nostr::PublicKey, req: &nip46::NostrConnectRequest) -> bool {
+        let req: NostrConnectRequest = req.clone().into();
+
+        // Deny requests not covered by the permissions, without asking
+        if !self.permissions.is_empty() && !NostrConnectPermission::allows(&self.permissions, &req)
+        {
+            return false;
+        }
+
+        self.inner.approve(Arc::new((*public_key).into()), req)
+    }
+}
+
+/// Nostr Connect Remote Signer
+///
+/// <https://github.com/nostr-protocol/nips/blob/master/46.md>
+#[derive(Object)]
+pub struct NostrConnectRemoteSigner {
+    inner: signer::NostrConnectRemoteSigner,
+}
+
+impl Deref for NostrConnectRemoteSigner {
+    type Target = signer::NostrConnectRemoteSigner;
+
+    fn deref(&self) -> &Self::Target {
+        &self.inner
+    }
+}
+
+#[uniffi::export(async_runtime = "tokio")]
+impl NostrConnectRemoteSigner {
+    /// Construct a new remote signer
+    ///
+    /// If `secret` is set, the `connect` requests that don't include it are rejected.
+    #[uniffi::constructor(default(secret = None, opts = None))]
+    pub fn new(
+        keys: NostrConnectKeys,
+        relays: Vec<Arc<RelayUrl>>,
+        secret: Option<String>,
+        opts: Option<Arc<RelayOptions>>,
+    ) -> Result<Self> {
+        Ok(Self {
+            inner: signer::NostrConnectRemoteSigner::new(
+                keys.into(),
+                relays.into_iter().map(|u| u.as_ref().deref().clone()),
+                secret,
+                opts.map(|o| o.as_ref().deref().clone()),
+            )?,
+        })
+    }
+
+    /// Construct a remote signer from a client URI (`nostrconnect://`)
+    #[uniffi::constructor(default(secret = None, opts = None))]
+    pub fn from_uri(
+        uri: &NostrConnectUri,
+        keys: NostrConnectKeys,
+        secret: Option<String>,
+        opts: Option<Arc<RelayOptions>>,
+    ) -> Result<Self> {
+        Ok(Self {
+            inner: signer::NostrConnectRemoteSigner::from_uri(
+                uri.deref().clone(),
+                keys.into(),
+                secret,
+                opts.map(|o| o.as_ref().deref().clone()),
+            )?,
+        })
+    }
+
+    /// Get signer relays
+    pub fn relays(&self) -> Vec<Arc<RelayUrl>> {
+        self.inner
+            .relays()
+            .iter()
+            .cloned()
+            .map(|u| Arc::new(u.into()))
+            .collect()
+    }
+
+    /// Get `bunker` URI
+    pub fn bunker_uri(&self) -> NostrConnectUri {
+        self.inner.bunker_uri().into()
+    }
+
+    /// Serve requests
+    ///
+    /// If `permissions` isn't empty, the requests not covered by them are denied
+    /// without calling the `actions` (`connect` and `ping` are always forwarded).
+    #[uniffi::method(default(permissions = []))]
+    pub async fn serve(
+        &self,
+        actions: Arc<dyn NostrConnectSignerActions>,
+        permissions: Vec<NostrConnectPermission>,
+    ) -> Result<()> {
+        let actions = FFI2RustNostrConnectSignerActions {
+            inner: actions,
+            permissions,
+        };
+        Ok(self.inner.serve(actions).await?)
+    }
+}
@@ -29,22 +29,28 @@ impl Events {
     where
         F: FnOnce(&mut prelude::Events) -> T,
     {
-        let mut inner = self.inner.lock()?;
+        let mut inner = self
+            .inner
+            .lock()
+            .map_err(|e| NostrSdkError::Database { msg: e.to_string() })?;
         match inner.as_mut() {
             Some(inner) => Ok(f(inner)),
-            None => Err(NostrSdkError::Generic(
-                "Events object already consumed".to_string(),
-            )),
+            None => Err(NostrSdkError::Database {
+                msg: "Events object already consumed".to_string(),
+            }),
         }
     }
 
     fn take(&self) -> Result<prelude::Events> {
-        let mut inner = self.inner.lock()?;
+        let mut inner = self
+            .inner
+            .lock()
+            .map_err(|e| NostrSdkError::Database { msg: e.to_string() })?;
         match inner.take() {
             Some(inner) => Ok(inner),
-            None => Err(NostrSdkError::Generic(
-                "Events object already consumed".to_string(),
-            )),
+            None => Err(NostrSdkError::Database {
+                msg: "Events object already consumed".to_string(),
+            }),
         }
     }
 }
@@ -12,8 +12,65 @@ pub type Result<T, E = NostrSdkError> = std::result::Result<T, E>;
 // as it will cause the following panic in callbacks: "Can't lift flat errors"
 #[derive(Debug, Error)]
 pub enum NostrSdkError {
+    /// Uncategorized error
     Generic(String),
     NonZeroDifficulty,
+    /// Relay or relay pool error (i.e., relay not found, connection failure, rejected message)
+    Relay {
+        msg: String,
+    },
+    /// Database error
+    Database {
+        msg: String,
+    },
+    /// Signer error (i.e., signer rejection or unsupported operation)
+    Signer {
+        msg: String,
+    },
+    /// Operation timed out
+    Timeout {
+        msg: String,
+    },
+    /// Keys error (i.e., invalid secret or public key)
+    Keys {
+        msg: String,
+    },
+    /// Event error (i.e., invalid signature, malformed event or tag)
+    Event {
+        msg: String,
+    },
+    /// Malformed URL
+    Url {
+        msg: String,
+    },
+    /// JSON (de)serialization error
+    Json {
+        msg: String,
+    },
+    /// Admission policy error
+    Policy {
+        msg: String,
+    },
+    /// NIP-04 error
+    Nip04 {
+        msg: String,
+    },
+    /// NIP-19 error (i.e., malformed bech32)
+    Nip19 {
+        msg: String,
+    },
+    /// NIP-44 error
+    Nip44 {
+        msg: String,
+    },
+    /// NIP-46 (Nostr Connect) error
+    Nip46 {
+        msg: String,
+    },
+    /// NIP-47 (Nostr Wallet Connect) error
+    Nip47 {
+        msg: String,
+    },
 }
 
 impl fmt::Display for NostrSdkError {
@@ -21,16 +78,191 @@ impl fmt::Display for NostrSdkError {
         match self {
             Self::Generic(e) => f.write_str(e),
             Self::NonZeroDifficulty => f.write_str("difficulty must be non-zero"),
+            Self::Relay { msg }
+            | Self::Database { msg }
+            | Self::Signer { msg }
+            | Self::Timeout { msg }
+            | Self::Keys { msg }
+            | Self::Event { msg }
+            | Self::Url { msg }
+            | Self::Json { msg }
+            | Self::Policy { msg }
+            | Self::Nip04 { msg }
+            | Self::Nip19 { msg }
+            | Self::Nip44 { msg }
+            | Self::Nip46 { msg }
+            | Self::Nip47 { msg } => f.write_str(msg),
         }
     }
 }
 
 impl<T> From<T> for NostrSdkError
 where
-    T: std::error::Error,
+    T: std::error::Error + 'static,
 {
     fn from(e: T) -> NostrSdkError {
-        Self::Generic(e.to_string())
+        let msg: String = e.to_string();
+
+        // Categorize by the innermost known error, so wrapped errors (i.e., a signer error returned by the client)
+        // are not labelled by their wrapper.
+        let mut category: Option<Category> = None;
+        let root: &(dyn std::error::Error + 'static) = &e;
+        let mut error: Option<&(dyn std::error::Error + 'static)> = Some(root);
+
+        while let Some(e) = error {
+            if let Some(c) = Category::of(e) {
+                category = Some(c);
+            }
+
+            error = e.source();
+        }
+
+        match category {
+            Some(category) => category.into_error(msg),
+            None => Self::Generic(msg),
+        }
+    }
+}
+
+impl NostrSdkError {
+    /// Stable machine-readable error code
+    pub(crate) fn as_code(&self) -> &'static str {
+        match self {
+            Self::Generic(..) => "generic",
+            Self::NonZeroDifficulty => "non_zero_difficulty",
+            Self::Relay { .. } => "relay",
+            Self::Database { .. } => "database",
+            Self::Signer { .. } => "signer",
+            Self::Timeout { .. } => "timeout",
+            Self::Keys { .. } => "keys",
+            Self::Event { .. } => "event",
+            Self::Url { .. } => "url",
+            Self::Json { .. } => "json",
+            Self::Policy { .. } => "policy",
+            Self::Nip04 { .. } => "nip04",
+            Self::Nip19 { .. } => "nip19",
+            Self::Nip44 { .. } => "nip44",
+            Self::Nip46 { .. } => "nip46",
+            Self::Nip47 { .. } => "nip47",
+        }
+    }
+}
+
+#[uniffi::export]
+impl NostrSdkError {
+    /// Get the stable machine-readable error code (i.e., `relay`, `signer`, `timeout`)
+    ///
+    /// Unlike the message, the code doesn't change between releases.
+    pub fn code(&self) -> String {
+        self.as_code().to_string()
+    }
+}
+
+#[derive(Clone, Copy)]
+enum Category {
+    Relay,
+    Database,
+    Signer,
+    Timeout,
+    Keys,
+    Event,
+    Url,
+    Json,
+    #[cfg(feature = "nip04")]
+    Nip04,
+    Nip19,
+    #[cfg(feature = "nip44")]
+    Nip44,
+    #[cfg(feature = "connect")]
+    Nip46,
+    #[cfg(any(feature = "nip47", feature = "nwc"))]
+    Nip47,
+}
+
+impl Category {
+    /// Map the concrete upstream error types
+    fn of(e: &(dyn std::error::Error + 'static)) -> Option<Self> {
+        macro_rules! map {
+            ($($ty:ty => $category:expr),* $(,)?) => {
+                $(
+                    if e.is::<$ty>() {
+                        return Some($category);
+                    }
+                )*
+            };
+        }
+
+        // Upstream timeout variants
+        if let Some(e) = e.downcast_ref::<nostr_sdk::error::Error>() {
+            if matches!(
+                e,
+                nostr_sdk::error::Error::Timeout { .. }
+                    | nostr_sdk::error::Error::RecvTimeout { .. }
+            ) {
+                return Some(Self::Timeout);
+            }
+        }
+
+        #[cfg(feature = "connect")]
+        if let Some(nostr_connect::error::Error::Timeout { .. }) = e.downcast_ref() {
+            return Some(Self::Timeout);
+        }
+
+        #[cfg(feature = "nwc")]
+        if let Some(nwc::error::Error::Timeout { .. }) = e.downcast_ref() {
+            return Some(Self::Timeout);
+        }
+
+        map! {
+            nostr::serde_json::Error => Self::Json,
+            nostr::key::Error => Self::Keys,
+            nostr::event::Error => Self::Event,
+            nostr::event::tag::Error => Self::Event,
+            nostr::types::url::Error => Self::Url,
+            nostr::signer::SignerError => Self::Signer,
+            nostr::nips::nip19::Error => Self::Nip19,
+            nostr_database::error::Error => Self::Database,
+            nostr_sdk::error::Error => Self::Relay,
+        }
+
+        #[cfg(feature = "nip04")]
+        map! { nostr::nips::nip04::Error => Self::Nip04 }
+
+        #[cfg(feature = "nip44")]
+        map! { nostr::nips::nip44::Error => Self::Nip44 }
+
+        #[cfg(feature = "connect")]
+        map! { nostr_connect::error::Error => Self::Nip46 }
+
+        #[cfg(feature = "nip47")]
+        map! { nostr::nips::nip47::Error => Self::Nip47 }
+
+        #[cfg(feature = "nwc")]
+        map! { nwc::error::Error => Self::Nip47 }
+
+        None
+    }
+
+    fn into_error(self, msg: String) -> NostrSdkError {
+        match self {
+            Self::Relay => NostrSdkError::Relay { msg },
+            Self::Database => NostrSdkError::Database { msg },
+            Self::Signer => NostrSdkError::Signer { msg },
+            Self::Timeout => NostrSdkError::Timeout { msg },
+            Self::Keys => NostrSdkError::Keys { msg },
+            Self::Event => NostrSdkError::Event { msg },
+            Self::Url => NostrSdkError::Url { msg },
+            Self::Json => NostrSdkError::Json { msg },
+            #[cfg(feature = "nip04")]
+            Self::Nip04 => NostrSdkError::Nip04 { msg },
+            Self::Nip19 => NostrSdkError::Nip19 { msg },
+            #[cfg(feature = "nip44")]
+            Self::Nip44 => NostrSdkError::Nip44 { msg },
+            #[cfg(feature = "connect")]
+            Self::Nip46 => NostrSdkError::Nip46 { msg },
+            #[cfg(any(feature = "nip47", feature = "nwc"))]
+            Self::Nip47 => NostrSdkError::Nip47 { msg },
+        }
     }
 }
 
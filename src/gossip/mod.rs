@@ -1,15 +1,119 @@
+use std::collections::HashSet;
 use std::ops::Deref;
 use std::sync::Arc;
 
 use nostr_gossip_memory::store::NostrGossipMemory;
 #[cfg(all(feature = "gossip-sqlite", not(target_arch = "wasm32")))]
 use nostr_gossip_sqlite::store::NostrGossipSqlite;
-use uniffi::Object;
+use nostr_sdk::prelude;
+use uniffi::{Enum, Object, Record};
 
 use crate::error::Result;
 use crate::protocol::event::Event;
+use crate::protocol::key::PublicKey;
 use crate::protocol::types::RelayUrl;
 
+/// Allowed gossip relay types during selection
+#[derive(Record)]
+pub struct GossipAllowedRelays {
+    /// Allow tor onion relays (default: true)
+    #[uniffi(default = true)]
+    pub onion: bool,
+    /// Allow local network relays (default: false)
+    #[uniffi(default = false)]
+    pub local: bool,
+    /// Allow relays without SSL/TLS encryption (default: true)
+    #[uniffi(default = true)]
+    pub without_tls: bool,
+}
+
+impl From<GossipAllowedRelays> for prelude::GossipAllowedRelays {
+    fn from(allowed: GossipAllowedRelays) -> Self {
+        Self {
+            onion: allowed.onion,
+            local: allowed.local,
+            without_tls: allowed.without_tls,
+        }
+    }
+}
+
+/// Gossip list kind
+#[derive(Enum)]
+pub enum GossipListKind {
+    /// NIP-17 relay list
+    Nip17,
+    /// NIP-65 relay list
+    Nip65,
+}
+
+impl From<GossipListKind> for nostr_gossip::GossipListKind {
+    fn from(kind: GossipListKind) -> Self {
+        match kind {
+            GossipListKind::Nip17 => Self::Nip17,
+            GossipListKind::Nip65 => Self::Nip65,
+        }
+    }
+}
+
+/// Best relay selection
+#[derive(Enum)]
+pub enum BestRelaySelection {
+    /// Get all the best relays for **reading** and **writing** events (NIP-65)
+    All {
+        /// Limit for read relays
+        read: u64,
+        /// Limit for write relays
+        write: u64,
+        /// Limit for hints
+        hints: u64,
+        /// Limit for most received relays
+        most_received: u64,
+    },
+    /// Get the best relays for **reading** events (NIP-65)
+    Read { limit: u64 },
+    /// Get the best relays for **writing** events (NIP-65)
+    Write { limit: u64 },
+    /// Get the best relays for **reading** and **writing** private messages (NIP-17)
+    PrivateMessage { limit: u64 },
+    /// Get the best relays that appear in **hints**
+    Hints { limit: u64 },
+    /// Get the relays from which the most events have been received
+    MostReceived { limit: u64 },
+}
+
+impl From<BestRelaySelection> for nostr_gossip::BestRelaySelection {
+    fn from(selection: BestRelaySelection) -> Self {
+        match selection {
+            BestRelaySelection::All {
+                read,
+                write,
+                hints,
+                most_received,
+            } => Self::All {
+                read: read as usize,
+                write: write as usize,
+                hints: hints as usize,
+                most_received: most_received as usize,
+            },
+            BestRelaySelection::Read { limit } => Self::Read {
+                limit: limit as usize,
+            },
+            BestRelaySelection::Write { limit } => Self::Write {
+                limit: limit as usize,
+            },
+            BestRelaySelection::PrivateMessage { limit } => Self::PrivateMessage {
+                limit: limit as usize,
+            },
+            BestRelaySelection::Hints { limit } => Self::Hints {
+                limit: limit as usize,
+            },
+            BestRelaySelection::MostReceived { limit } => Self::MostReceived {
+                limit: limit as usize,
+            },
+        }
+    }
+}
+
 #[derive(Object)]
 pub struct NostrGossip {
     inner: Arc<dyn nostr_gossip::NostrGossip>,
@@ -47,6 +151,44 @@ impl NostrGossip {
         self.inner.process(event.deref(), relay_url).await?;
         Ok(())
     }
+
+    /// Get the best relays for a public key
+    ///
+    /// These are the relays the client would pick for the outbox model.
+    #[uniffi::method(default(allowed = None))]
+    pub async fn get_best_relays(
+        &self,
+        public_key: &PublicKey,
+        selection: BestRelaySelection,
+        allowed: Option<GossipAllowedRelays>,
+    ) -> Result<Vec<Arc<RelayUrl>>> {
+        let allowed: prelude::GossipAllowedRelays = allowed.map(|a| a.into()).unwrap_or_default();
+        let relays: HashSet<nostr::RelayUrl> = self
+            .inner
+            .get_best_relays(public_key.deref(), selection.into(), allowed)
+            .await?;
+        Ok(relays.into_iter().map(|u| Arc::new(u.into())).collect())
+    }
+
+    /// Get the public keys whose relay list is outdated, or never fetched
+    pub async fn outdated_public_keys(
+        &self,
+        public_keys: Vec<Arc<PublicKey>>,
+        list: GossipListKind,
+    ) -> Result<Vec<Arc<PublicKey>>> {
+        let list: nostr_gossip::GossipListKind = list.into();
+        let mut outdated: Vec<Arc<PublicKey>> = Vec::new();
+
+        for public_key in public_keys.into_iter() {
+            let status = self.inner.status(public_key.deref(), list).await?;
+
+            if let nostr_gossip::GossipPublicKeyStatus::Outdated { .. } = status {
+                outdated.push(public_key);
+            }
+        }
+
+        Ok(outdated)
+    }
 }
 
 #[cfg(all(feature = "gossip-sqlite", not(target_arch = "wasm32")))]
@@ -1,57 +1,89 @@
+#[cfg(not(target_arch = "wasm32"))]
+use std::collections::HashMap;
+use std::collections::HashSet;
 use std::ops::Deref;
+use std::pin::Pin;
 use std::sync::Arc;
+use std::time::Duration;
 
-use nostr_sdk::monitor;
-use uniffi::Object;
+use futures_util::{Stream, StreamExt, stream};
+use nostr_sdk::{client, monitor, relay};
+use tokio::sync::Mutex;
+use tokio::sync::broadcast::error::RecvError;
+use uniffi::{Enum, Object};
 
+use crate::authenticator::SentAuthEvents;
 use crate::error::Result;
 use crate::protocol::types::RelayUrl;
 use crate::relay::RelayStatus;
 
-// /// Monitor notification
-// #[derive(Enum)]
-// pub enum MonitorNotification {
-//     /// Relay status changed
-//     StatusChanged {
-//         /// Relay URL
-//         relay_url: Arc<RelayUrl>,
-//         /// Status
-//         status: RelayStatus,
-//     },
-// }
-//
-// impl From<monitor::MonitorNotification> for MonitorNotification {
-//     fn from(notification: monitor::MonitorNotification) -> Self {
-//         match notification {
-//             monitor::MonitorNotification::StatusChanged { relay_url, status } => Self::StatusChanged {
-//                 relay_url: Arc::new(relay_url.into()),
-//                 status: status.into(),
-//             },
-//         }
-//     }
-// }
-
-// #[derive(Object)]
-// pub struct MonitorNotificationStream {
-//     stream: Mutex<BoxedStream<monitor::MonitorNotification>>,
-// }
-//
-// impl From<BoxedStream<monitor::MonitorNotification>> for MonitorNotificationStream {
-//     fn from(stream: BoxedStream<monitor::MonitorNotification>) -> Self {
-//         Self {
-//             stream: Mutex::new(stream),
-//         }
-//     }
-// }
-//
-// #[uniffi::export(async_runtime = "tokio")]
-// impl MonitorNotificationStream {
-//     pub async fn next(&self) -> Option<MonitorNotification> {
-//         let mut stream = self.stream.lock().await;
-//         let notification: monitor::MonitorNotification = stream.next().await?;
-//         Some(notification.into())
-//     }
-// }
+/// Monitor notification
+#[derive(Enum)]
+pub enum MonitorNotification {
+    /// Relay status changed
+    ///
+    /// A ban is notified with `Banned` instead.
+    StatusChanged {
+        /// Relay URL
+        relay_url: Arc<RelayUrl>,
+        /// Status
+        status: RelayStatus,
+    },
+    /// Relay has been banned
+    ///
+    /// Emitted once, when the relay status changes to banned.
+    Banned {
+        /// Relay URL
+        relay_url: Arc<RelayUrl>,
+    },
+    /// Relay latency updated
+    ///
+    /// Emitted when the latency measured for a relay changes, only by the monitors obtained with `Client::monitor`.
+    LatencyUpdated {
+        /// Relay URL
+        relay_url: Arc<RelayUrl>,
+        /// Latency
+        latency: Duration,
+    },
+    /// Authentication (NIP-42) succeeded
+    ///
+    /// Emitted only by the monitors obtained with `Client::monitor`,
+    /// when the relay accepts the `AUTH` event made by the `ClientBuilder::authenticator`.
+    AuthSucceeded {
+        /// Relay URL
+        relay_url: Arc<RelayUrl>,
+    },
+    /// Authentication (NIP-42) failed
+    ///
+    /// Emitted only by the monitors obtained with `Client::monitor`,
+    /// when the relay rejects the `AUTH` event made by the `ClientBuilder::authenticator`.
+    AuthFailed {
+        /// Relay URL
+        relay_url: Arc<RelayUrl>,
+        /// Reason reported by the relay
+        message: String,
+    },
+}
+
+/// Interval between two checks of the relays latency
+#[cfg(not(target_arch = "wasm32"))]
+const LATENCY_CHECK_INTERVAL: Duration = Duration::from_secs(5);
+
+#[derive(Object)]
+pub struct MonitorNotificationStream {
+    stream: Mutex<Pin<Box<dyn Stream<Item = MonitorNotification> + Send>>>,
+}
+
+#[uniffi::export(async_runtime = "tokio")]
+impl MonitorNotificationStream {
+    /// Consumes the next item from the stream.
+    ///
+    /// Returns null if the stream is terminated.
+    pub async fn next(&self) -> Option<MonitorNotification> {
+        let mut stream = self.stream.lock().await;
+        stream.next().await
+    }
+}
 
 #[uniffi::export(with_foreign)]
 #[async_trait::async_trait]
@@ -62,6 +94,10 @@ pub trait HandleMonitorNotification: Send + Sync {
 #[derive(Object)]
 pub struct Monitor {
     inner: monitor::Monitor,
+    /// Client the monitor is attached to, used to enrich the notifications
+    client: Option<client::Client>,
+    /// Authentication events sent by the client
+    auth_events: SentAuthEvents,
 }
 
 impl Deref for Monitor {
@@ -74,29 +110,216 @@ impl Deref for Monitor {
 
 impl From<monitor::Monitor> for Monitor {
     fn from(inner: monitor::Monitor) -> Self {
-        Self { inner }
+        Self {
+            inner,
+            client: None,
+            auth_events: SentAuthEvents::default(),
+        }
+    }
+}
+
+impl Monitor {
+    pub(crate) fn with_client(
+        inner: monitor::Monitor,
+        client: client::Client,
+        auth_events: SentAuthEvents,
+    ) -> Self {
+        Self {
+            inner,
+            client: Some(client),
+            auth_events,
+        }
+    }
+}
+
+/// Map a status notification
+///
+/// The relays already banned are tracked in `banned`, so that a ban is notified only once.
+fn map_notification(
+    notification: monitor::MonitorNotification,
+    banned: &mut HashSet<nostr::RelayUrl>,
+) -> Option<MonitorNotification> {
+    match notification {
+        monitor::MonitorNotification::StatusChanged { relay_url, status } => {
+            if matches!(status, relay::RelayStatus::Banned) {
+                if !banned.insert(relay_url.clone()) {
+                    return None;
+                }
+
+                return Some(MonitorNotification::Banned {
+                    relay_url: Arc::new(relay_url.into()),
+                });
+            }
+
+            banned.remove(&relay_url);
+
+            Some(MonitorNotification::StatusChanged {
+                relay_url: Arc::new(relay_url.into()),
+                status: status.into(),
+            })
+        }
+    }
+}
+
+/// Authentication results, taken from the `OK` messages of the `AUTH` events sent by the client
+fn auth_notifications(
+    client: client::Client,
+    auth_events: SentAuthEvents,
+) -> impl Stream<Item = MonitorNotification> + Send {
+    let notifications = client.notifications();
+    stream::unfold(
+        (notifications, auth_events),
+        |(mut notifications, auth_events)| async move {
+            while let Some(notification) = notifications.next().await {
+                let (relay_url, message) = match notification {
+                    client::ClientNotification::Message {
+                        relay_url, message, ..
+                    } => (relay_url, message),
+                    client::ClientNotification::Shutdown => return None,
+                    _ => continue,
+                };
+
+                let nostr::RelayMessage::Ok {
+                    event_id,
+                    status,
+                    message,
+                } = message.as_ref()
+                else {
+                    continue;
+                };
+
+                if !auth_events.take(&relay_url, event_id).await {
+                    continue;
+                }
+
+                let relay_url: Arc<RelayUrl> = Arc::new(relay_url.into());
+                let notification: MonitorNotification = if *status {
+                    MonitorNotification::AuthSucceeded { relay_url }
+                } else {
+                    MonitorNotification::AuthFailed {
+                        relay_url,
+                        message: message.to_string(),
+                    }
+                };
+
+                return Some((notification, (notifications, auth_events)));
+            }
+
+            None
+        },
+    )
+}
+
+/// Latency updates, checked periodically for the relays of the client
+#[cfg(not(target_arch = "wasm32"))]
+fn latency_notifications(client: client::Client) -> impl Stream<Item = MonitorNotification> + Send {
+    let interval = tokio::time::interval(LATENCY_CHECK_INTERVAL);
+    stream::unfold(
+        (interval, client, HashMap::new()),
+        |(mut interval, client, mut latencies)| async move {
+            loop {
+                interval.tick().await;
+
+                let mut notifications: Vec<MonitorNotification> = Vec::new();
+
+                for (relay_url, relay) in client.relays().await.into_iter() {
+                    let Some(latency) = relay.stats().latency() else {
+                        continue;
+                    };
+
+                    if latencies.insert(relay_url.clone(), latency) != Some(latency) {
+                        notifications.push(MonitorNotification::LatencyUpdated {
+                            relay_url: Arc::new(relay_url.into()),
+                            latency,
+                        });
+                    }
+                }
+
+                if !notifications.is_empty() {
+                    return Some((stream::iter(notifications), (interval, client, latencies)));
+                }
+            }
+        },
+    )
+    .flatten()
+}
+
+/// Notifications derived from the client the monitor is attached to, until the client shuts down
+fn client_notifications(
+    client: client::Client,
+    auth_events: SentAuthEvents,
+) -> Pin<Box<dyn Stream<Item = MonitorNotification> + Send>> {
+    #[cfg(not(target_arch = "wasm32"))]
+    {
+        let mut notifications = client.notifications();
+        let shutdown = async move {
+            while let Some(notification) = notifications.next().await {
+                if let client::ClientNotification::Shutdown = notification {
+                    break;
+                }
+            }
+        };
+
+        Box::pin(
+            stream::select(
+                auth_notifications(client.clone(), auth_events),
+                latency_notifications(client),
+            )
+            .take_until(shutdown),
+        )
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    {
+        Box::pin(auth_notifications(client, auth_events))
     }
 }
 
-#[uniffi::export]
+#[uniffi::export(async_runtime = "tokio")]
 impl Monitor {
     /// Create a new monitor with the given channel size
     #[uniffi::constructor(default(channel_size = 4096))]
     pub fn new(channel_size: u64) -> Self {
-        Self {
-            inner: monitor::Monitor::new(channel_size as usize),
-        }
+        monitor::Monitor::new(channel_size as usize).into()
     }
 
-    // /// Subscribe to monitor notifications
-    // ///
-    // /// <div class="warning">When you call this method, you subscribe to the notifications channel from that precise moment. Anything received by relay/s before that moment is not included in the channel!</div>
-    // pub fn subscribe(&self) -> MonitorNotificationStream {
-    //     let receiver = self.inner.subscribe();
-    //     let stream: BroadcastStream<monitor::MonitorNotification> = BroadcastStream::new(receiver);
-    //     let boxed: BoxedStream<monitor::MonitorNotification> = Box::pin(stream);
-    //     boxed.into()
-    // }
+    /// Subscribe to monitor notifications
+    ///
+    /// Lagged notifications are skipped.
+    ///
+    /// <div class="warning">When you call this method, you subscribe to the notifications channel from that precise moment. Anything received by relay/s before that moment is not included in the channel!</div>
+    pub fn subscribe(&self) -> MonitorNotificationStream {
+        let receiver = self.inner.subscribe();
+        let status = stream::unfold(
+            (receiver, HashSet::new()),
+            |(mut receiver, mut banned)| async move {
+                loop {
+                    match receiver.recv().await {
+                        Ok(notification) => {
+                            if let Some(notification) = map_notification(notification, &mut banned)
+                            {
+                                return Some((notification, (receiver, banned)));
+                            }
+                        }
+                        Err(RecvError::Lagged(..)) => continue,
+                        Err(RecvError::Closed) => return None,
+                    }
+                }
+            },
+        );
+
+        let stream: Pin<Box<dyn Stream<Item = MonitorNotification> + Send>> = match &self.client {
+            Some(client) => Box::pin(stream::select(
+                status,
+                client_notifications(client.clone(), self.auth_events.clone()),
+            )),
+            None => Box::pin(status),
+        };
+
+        MonitorNotificationStream {
+            stream: Mutex::new(stream),
+        }
+    }
 
     /// Handle notifications
     pub async fn handle_notifications(
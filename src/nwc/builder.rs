@@ -1,17 +1,22 @@
 use std::ops::Deref;
 use std::time::Duration;
 
+use nostr::nips::nip47;
 use nwc::builder;
 use uniffi::Object;
 
 use super::NostrWalletConnect;
+use super::guard::{BudgetRenewal, SpendingLimits};
 use crate::monitor::Monitor;
-use crate::protocol::nips::nip47::NostrWalletConnectUri;
+use crate::protocol::nips::nip47::{Method, NostrWalletConnectUri};
 use crate::relay::RelayOptions;
 
 #[derive(Clone, Object)]
 pub struct NostrWalletConnectBuilder {
     inner: builder::NostrWalletConnectBuilder,
+    uri: nip47::NostrWalletConnectUri,
+    timeout: Duration,
+    limits: SpendingLimits,
 }
 
 impl Deref for NostrWalletConnectBuilder {
@@ -29,6 +34,9 @@ impl NostrWalletConnectBuilder {
     pub fn new(uri: &NostrWalletConnectUri) -> Self {
         Self {
             inner: builder::NostrWalletConnectBuilder::new(uri.deref().clone()),
+            uri: uri.deref().clone(),
+            timeout: super::DEFAULT_TIMEOUT,
+            limits: SpendingLimits::default(),
         }
     }
 
@@ -36,6 +44,7 @@ impl NostrWalletConnectBuilder {
     pub fn timeout(&self, timeout: Duration) -> Self {
         let mut builder = self.clone();
         builder.inner = builder.inner.timeout(timeout);
+        builder.timeout = timeout;
         builder
     }
 
@@ -53,8 +62,39 @@ impl NostrWalletConnectBuilder {
         builder
     }
 
+    /// Set a spending budget, in millisats
+    ///
+    /// Payments exceeding the remaining budget of the current period are rejected locally,
+    /// before reaching the wallet. Fees are counted once paid.
+    pub fn budget(&self, msats: u64, renewal: BudgetRenewal) -> Self {
+        let mut builder = self.clone();
+        builder.limits = builder.limits.budget(msats, renewal);
+        builder
+    }
+
+    /// Set the max amount of a single payment, in millisats
+    pub fn max_payment(&self, msats: u64) -> Self {
+        let mut builder = self.clone();
+        builder.limits.max_payment = Some(msats);
+        builder
+    }
+
+    /// Allow only the specified methods
+    ///
+    /// Calls to other methods are rejected locally, before reaching the wallet.
+    pub fn allowed_methods(&self, methods: Vec<Method>) -> Self {
+        let mut builder = self.clone();
+        builder.limits.allowed_methods = Some(methods.into_iter().map(|m| m.into()).collect());
+        builder
+    }
+
     #[inline]
     pub fn build(&self) -> NostrWalletConnect {
-        self.inner.clone().build().into()
+        NostrWalletConnect::from_builder(
+            self.inner.clone().build(),
+            self.uri.clone(),
+            self.timeout,
+            self.limits.clone(),
+        )
     }
 }
@@ -0,0 +1,174 @@
+// Copyright (c) 2023-2025 Rust Nostr Developers
+// Distributed under the MIT software license
+
+//! Client-side spending guard
+//!
+//! Reject the requests exceeding the configured limits before they reach the wallet.
+
+use std::time::Duration;
+
+use nostr::nips::nip47;
+use tokio::sync::Mutex;
+use uniffi::Enum;
+
+use crate::error::{NostrSdkError, Result};
+
+const DAY: Duration = Duration::from_secs(60 * 60 * 24);
+
+/// Budget renewal period
+#[derive(Enum)]
+pub enum BudgetRenewal {
+    /// Renew the budget every 24 hours
+    Daily,
+    /// Renew the budget every 7 days
+    Weekly,
+}
+
+impl BudgetRenewal {
+    fn period(&self) -> Duration {
+        match self {
+            Self::Daily => DAY,
+            Self::Weekly => DAY * 7,
+        }
+    }
+}
+
+/// Spending limits
+#[derive(Debug, Clone, Default)]
+pub(crate) struct SpendingLimits {
+    /// Budget in millisats and renewal period
+    pub budget: Option<(u64, Duration)>,
+    /// Max amount of a single payment, in millisats
+    pub max_payment: Option<u64>,
+    /// Allowed methods (all if `None`)
+    pub allowed_methods: Option<Vec<nip47::Method>>,
+}
+
+impl SpendingLimits {
+    pub fn budget(mut self, amount: u64, renewal: BudgetRenewal) -> Self {
+        self.budget = Some((amount, renewal.period()));
+        self
+    }
+
+    pub fn is_unlimited(&self) -> bool {
+        self.budget.is_none() && self.max_payment.is_none()
+    }
+}
+
+#[derive(Debug)]
+struct BudgetPeriod {
+    /// Spent millisats in the current period
+    spent: u64,
+    /// Start of the current period
+    started_at: nostr::Timestamp,
+}
+
+#[derive(Debug)]
+pub(crate) struct SpendingGuard {
+    limits: SpendingLimits,
+    period: Mutex<BudgetPeriod>,
+}
+
+impl SpendingGuard {
+    pub fn new(limits: SpendingLimits) -> Self {
+        Self {
+            limits,
+            period: Mutex::new(BudgetPeriod {
+                spent: 0,
+                started_at: nostr::Timestamp::now(),
+            }),
+        }
+    }
+
+    /// Check if the method is allowed
+    pub fn check_method(&self, method: nip47::Method) -> Result<()> {
+        match &self.limits.allowed_methods {
+            Some(allowed) if !allowed.contains(&method) => Err(NostrSdkError::Nip47 {
+                msg: format!("Method not allowed: {method:?}"),
+            }),
+            _ => Ok(()),
+        }
+    }
+
+    /// Check the payments against the limits and reserve their total amount
+    ///
+    /// Return the reserved amount, to [`SpendingGuard::release`] it if the wallet reports that the payments failed.
+    pub async fn reserve(&self, amounts: &[Option<u64>]) -> Result<u64> {
+        if self.limits.is_unlimited() {
+            return Ok(0);
+        }
+
+        let mut total: u64 = 0;
+
+        for amount in amounts.iter() {
+            let amount: u64 = amount.ok_or_else(|| NostrSdkError::Nip47 {
+                msg: String::from("Payment amount unknown: can't check the spending limits"),
+            })?;
+
+            if let Some(max) = self.limits.max_payment {
+                if amount > max {
+                    return Err(NostrSdkError::Nip47 {
+                        msg: format!("Payment of {amount} msats exceeds the max of {max} msats"),
+                    });
+                }
+            }
+
+            total = total.saturating_add(amount);
+        }
+
+        if let Some((budget, period)) = self.limits.budget {
+            let mut current = self.period.lock().await;
+            current.renew(period);
+
+            let remaining: u64 = budget.saturating_sub(current.spent);
+
+            if total > remaining {
+                return Err(NostrSdkError::Nip47 {
+                    msg: format!(
+                        "Payment of {total} msats exceeds the remaining budget of {remaining} msats"
+                    ),
+                });
+            }
+
+            current.spent = current.spent.saturating_add(total);
+        }
+
+        Ok(total)
+    }
+
+    /// Add spent millisats (i.e., fees) to the current period
+    pub async fn spend(&self, amount: u64) {
+        if self.limits.budget.is_some() {
+            let mut current = self.period.lock().await;
+            current.spent = current.spent.saturating_add(amount);
+        }
+    }
+
+    /// Release a reserved amount
+    pub async fn release(&self, amount: u64) {
+        if self.limits.budget.is_some() {
+            let mut current = self.period.lock().await;
+            current.spent = current.spent.saturating_sub(amount);
+        }
+    }
+
+    /// Get the remaining budget of the current period, in millisats
+    pub async fn remaining(&self) -> Option<u64> {
+        let (budget, period) = self.limits.budget?;
+        let mut current = self.period.lock().await;
+        current.renew(period);
+        Some(budget.saturating_sub(current.spent))
+    }
+}
+
+impl BudgetPeriod {
+    /// Start a new period if the current one is expired
+    fn renew(&mut self, period: Duration) {
+        let now: nostr::Timestamp = nostr::Timestamp::now();
+
+        if now.as_u64() >= self.started_at.as_u64().saturating_add(period.as_secs()) {
+            self.spent = 0;
+            self.started_at = now;
+        }
+    }
+}
@@ -2,24 +2,72 @@
 // Copyright (c) 2023-2025 Rust Nostr Developers
 // Distributed under the MIT software license
 
+use std::collections::HashMap;
 use std::ops::Deref;
+use std::pin::Pin;
+use std::time::Duration;
 
+use futures_util::{Stream, StreamExt, future};
+use nostr::nips::{nip04, nip44, nip47};
+use nostr::serde_json::{self, Value, json};
+use nostr_sdk::client;
+use nostr_sdk::prelude::ReqExitPolicy;
+use tokio::sync::{Mutex, OnceCell};
 use uniffi::Object;
 
 mod builder;
+mod guard;
+mod service;
 
+use self::guard::{SpendingGuard, SpendingLimits};
 use crate::client::Client;
 use crate::error::Result;
 use crate::protocol::nips::nip47::{
-    GetBalanceResponse, GetInfoResponse, ListTransactionsRequest, LookupInvoiceRequest,
-    LookupInvoiceResponse, MakeInvoiceRequest, MakeInvoiceResponse, NostrWalletConnectUri,
-    PayInvoiceRequest, PayInvoiceResponse, PayKeysendRequest, PayKeysendResponse,
+    CancelHoldInvoiceRequest, CancelHoldInvoiceResponse, GetBalanceResponse, GetInfoResponse,
+    ListTransactionsRequest, LookupInvoiceRequest, LookupInvoiceResponse, MakeHoldInvoiceRequest,
+    MakeHoldInvoiceResponse, MakeInvoiceRequest, MakeInvoiceResponse, MultiPayInvoiceResult,
+    MultiPayKeysendResult, NostrWalletConnectUri, PayInvoiceRequest, PayInvoiceResponse,
+    PayKeysendRequest, PayKeysendResponse, SettleHoldInvoiceRequest, SettleHoldInvoiceResponse,
+    WalletNotification,
 };
+use crate::protocol::nips::nip57::Bolt11Invoice;
+
+/// NIP-44 encrypted notification kind
+const NIP44_NOTIFICATION_KIND: u16 = 23197;
+/// Encryption tag name (`encryption`)
+const ENCRYPTION_TAG: &str = "encryption";
+/// NIP-44 encryption scheme name
+const NIP44_V2: &str = "nip44_v2";
+/// NIP-04 encryption scheme name
+const NIP04: &str = "nip04";
+/// Default requests timeout
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(10);
+
+#[derive(Object)]
+pub struct NostrWalletConnectNotificationStream {
+    stream: Mutex<Pin<Box<dyn Stream<Item = WalletNotification> + Send>>>,
+}
+
+#[uniffi::export(async_runtime = "tokio")]
+impl NostrWalletConnectNotificationStream {
+    /// Consumes the next item from the stream.
+    ///
+    /// Returns null if the stream is terminated.
+    pub async fn next(&self) -> Option<WalletNotification> {
+        let mut stream = self.stream.lock().await;
+        stream.next().await
+    }
+}
 
 /// Nostr Wallet Connect client
 #[derive(Object)]
 pub struct NostrWalletConnect {
     inner: nwc::NostrWalletConnect,
+    uri: nip47::NostrWalletConnectUri,
+    timeout: Duration,
+    guard: SpendingGuard,
+    /// Whether the wallet advertises NIP-44 support in its info event
+    nip44: OnceCell<bool>,
 }
 
 impl Deref for NostrWalletConnect {
@@ -30,9 +78,138 @@ impl Deref for NostrWalletConnect {
     }
 }
 
-impl From<nwc::NostrWalletConnect> for NostrWalletConnect {
-    fn from(inner: nwc::NostrWalletConnect) -> Self {
-        Self { inner }
+impl NostrWalletConnect {
+    pub(crate) fn from_builder(
+        inner: nwc::NostrWalletConnect,
+        uri: nip47::NostrWalletConnectUri,
+        timeout: Duration,
+        limits: SpendingLimits,
+    ) -> Self {
+        Self {
+            inner,
+            uri,
+            timeout,
+            guard: SpendingGuard::new(limits),
+            nip44: OnceCell::new(),
+        }
+    }
+
+    /// Add the URI relays and connect
+    async fn connect_relays(&self) -> Result<()> {
+        let nwc_client = self.inner.client();
+
+        for url in self.uri.relays.iter() {
+            nwc_client.add_relay(url).await?;
+        }
+
+        nwc_client.connect().await;
+
+        Ok(())
+    }
+
+    /// Check if the wallet supports NIP-44, from the `encryption` tag of its info event (kind `13194`)
+    ///
+    /// The result is cached. Wallets without info event or `encryption` tag use NIP-04.
+    async fn wallet_supports_nip44(&self) -> Result<bool> {
+        self.nip44
+            .get_or_try_init(|| async {
+                let filter: nostr::Filter = nostr::Filter::new()
+                    .author(self.uri.public_key)
+                    .kind(nostr::Kind::WalletConnectInfo)
+                    .limit(1);
+                let events = self
+                    .inner
+                    .client()
+                    .fetch_events(client::ReqTarget::auto([filter]))
+                    .timeout(self.timeout)
+                    .await?;
+
+                Ok(events
+                    .into_iter()
+                    .max_by_key(|e| e.created_at)
+                    .is_some_and(|info| advertises_nip44(&info)))
+            })
+            .await
+            .copied()
+    }
+
+    /// Send a multi-pay request and collect the responses, keyed by item ID
+    ///
+    /// The amounts are reserved just before sending the request, and released if it can't be sent.
+    /// Items without a response before the timeout are missing.
+    async fn multi_pay(
+        &self,
+        method: &str,
+        params: Value,
+        ids: &[String],
+        amounts: &[Option<u64>],
+    ) -> Result<HashMap<String, Result<Value, nip47::NIP47Error>>> {
+        self.connect_relays().await?;
+
+        let nwc_client = self.inner.client();
+        let app: nostr::Keys = nostr::Keys::new(self.uri.secret.clone());
+        let wallet: nostr::PublicKey = self.uri.public_key;
+
+        let request: Value = json!({ "method": method, "params": params });
+        let mut tags: Vec<nostr::Tag> = vec![nostr::Tag::public_key(wallet)];
+        let content: String = if self.wallet_supports_nip44().await? {
+            tags.push(nostr::Tag::custom(
+                ENCRYPTION_TAG,
+                [NIP44_V2.to_string()].as_slice(),
+            ));
+            nip44::encrypt(
+                app.secret_key(),
+                &wallet,
+                request.to_string(),
+                nip44::Version::V2,
+            )?
+        } else {
+            nip04::encrypt(app.secret_key(), &wallet, request.to_string())?
+        };
+        let event: nostr::Event =
+            nostr::EventBuilder::new(nostr::Kind::WalletConnectRequest, content)
+                .tags(tags)
+                .finalize(&app)?;
+
+        let filter: nostr::Filter = nostr::Filter::new()
+            .author(wallet)
+            .pubkey(app.public_key())
+            .kind(nostr::Kind::WalletConnectResponse)
+            .event(event.id);
+
+        // Subscribe before sending the request, to not miss any response
+        let mut stream = nwc_client
+            .stream_events(client::ReqTarget::auto([filter]))
+            .timeout(self.timeout)
+            .policy(ReqExitPolicy::WaitForEvents(
+                u16::try_from(ids.len()).unwrap_or(u16::MAX),
+            ))
+            .await?;
+
+        let reserved: u64 = self.guard.reserve(amounts).await?;
+
+        // The request wasn't sent: nothing will be paid
+        if let Err(e) = nwc_client.send_event(&event).await {
+            self.guard.release(reserved).await;
+            return Err(e.into());
+        }
+
+        let mut responses: HashMap<String, Result<Value, nip47::NIP47Error>> =
+            HashMap::with_capacity(ids.len());
+
+        while let Some((_, res)) = stream.next().await {
+            if let Ok(event) = res {
+                if let Some((id, response)) = parse_multi_pay_response(&app, &wallet, &event) {
+                    responses.insert(id, response);
+                }
+            }
+
+            if ids.iter().all(|id| responses.contains_key(id)) {
+                break;
+            }
+        }
+
+        Ok(responses)
     }
 }
 
@@ -45,6 +222,10 @@ impl NostrWalletConnect {
     pub fn new(uri: &NostrWalletConnectUri) -> Self {
         Self {
             inner: nwc::NostrWalletConnect::new(uri.deref().clone()),
+            uri: uri.deref().clone(),
+            timeout: DEFAULT_TIMEOUT,
+            guard: SpendingGuard::new(SpendingLimits::default()),
+            nip44: OnceCell::new(),
         }
     }
 
@@ -54,18 +235,189 @@ impl NostrWalletConnect {
         self.inner.client().clone().into()
     }
 
+    /// Get the remaining budget of the current period, in millisats
+    ///
+    /// Returns null if no budget is set.
+    pub async fn remaining_budget(&self) -> Option<u64> {
+        self.guard.remaining().await
+    }
+
     /// Pay invoice
     pub async fn pay_invoice(&self, params: PayInvoiceRequest) -> Result<PayInvoiceResponse> {
-        Ok(self.inner.pay_invoice(params.into()).await?.into())
+        self.guard.check_method(nip47::Method::PayInvoice)?;
+
+        let params: nip47::PayInvoiceRequest = params.into();
+        let reserved: u64 = self.guard.reserve(&[invoice_amount(&params)]).await?;
+
+        match self.inner.pay_invoice(params).await {
+            Ok(response) => {
+                self.guard
+                    .spend(response.fees_paid.unwrap_or_default())
+                    .await;
+                Ok(response.into())
+            }
+            Err(e) => {
+                // On timeout the wallet may have paid: keep the reservation
+                if is_wallet_error(&e) {
+                    self.guard.release(reserved).await;
+                }
+                Err(e.into())
+            }
+        }
     }
 
     /// Pay keysend
     pub async fn pay_keysend(&self, params: PayKeysendRequest) -> Result<PayKeysendResponse> {
-        Ok(self.inner.pay_keysend(params.into()).await?.into())
+        self.guard.check_method(nip47::Method::PayKeysend)?;
+
+        let reserved: u64 = self.guard.reserve(&[Some(params.amount)]).await?;
+
+        match self.inner.pay_keysend(params.into()).await {
+            Ok(response) => {
+                self.guard
+                    .spend(response.fees_paid.unwrap_or_default())
+                    .await;
+                Ok(response.into())
+            }
+            Err(e) => {
+                // On timeout the wallet may have paid: keep the reservation
+                if is_wallet_error(&e) {
+                    self.guard.release(reserved).await;
+                }
+                Err(e.into())
+            }
+        }
+    }
+
+    /// Pay multiple invoices in a single request
+    ///
+    /// Return the result of each payment, keyed by the item `id`.
+    /// Items without an `id` get a random one.
+    ///
+    /// Requires the `PayInvoice` method to be allowed.
+    pub async fn multi_pay_invoice(
+        &self,
+        invoices: Vec<PayInvoiceRequest>,
+    ) -> Result<HashMap<String, MultiPayInvoiceResult>> {
+        self.guard.check_method(nip47::Method::PayInvoice)?;
+
+        let invoices: Vec<nip47::PayInvoiceRequest> = invoices
+            .into_iter()
+            .map(|mut invoice| {
+                invoice.id.get_or_insert_with(random_id);
+                invoice.into()
+            })
+            .collect();
+        let ids: Vec<String> = invoices.iter().filter_map(|i| i.id.clone()).collect();
+        let amounts: Vec<Option<u64>> = invoices.iter().map(invoice_amount).collect();
+        let params: Value = json!({ "invoices": invoices });
+
+        let mut responses = self
+            .multi_pay("multi_pay_invoice", params, &ids, &amounts)
+            .await?;
+        let mut results: HashMap<String, MultiPayInvoiceResult> = HashMap::with_capacity(ids.len());
+
+        for (id, amount) in ids.into_iter().zip(amounts.into_iter()) {
+            let result = match responses.remove(&id) {
+                Some(Ok(response)) => {
+                    match serde_json::from_value::<nip47::PayInvoiceResponse>(response) {
+                        Ok(response) => {
+                            self.guard
+                                .spend(response.fees_paid.unwrap_or_default())
+                                .await;
+                            MultiPayInvoiceResult::Paid {
+                                response: response.into(),
+                            }
+                        }
+                        // The wallet may have paid: keep the reservation
+                        Err(e) => MultiPayInvoiceResult::Failed {
+                            error: malformed(e).into(),
+                        },
+                    }
+                }
+                Some(Err(error)) => {
+                    self.guard.release(amount.unwrap_or_default()).await;
+                    MultiPayInvoiceResult::Failed {
+                        error: error.into(),
+                    }
+                }
+                // On timeout the wallet may have paid: keep the reservation
+                None => MultiPayInvoiceResult::Failed {
+                    error: no_response().into(),
+                },
+            };
+            results.insert(id, result);
+        }
+
+        Ok(results)
+    }
+
+    /// Pay multiple keysends in a single request
+    ///
+    /// Return the result of each payment, keyed by the item `id`.
+    /// Items without an `id` get a random one.
+    ///
+    /// Requires the `PayKeysend` method to be allowed.
+    pub async fn multi_pay_keysend(
+        &self,
+        keysends: Vec<PayKeysendRequest>,
+    ) -> Result<HashMap<String, MultiPayKeysendResult>> {
+        self.guard.check_method(nip47::Method::PayKeysend)?;
+
+        let keysends: Vec<nip47::PayKeysendRequest> = keysends
+            .into_iter()
+            .map(|mut keysend| {
+                keysend.id.get_or_insert_with(random_id);
+                keysend.into()
+            })
+            .collect();
+        let ids: Vec<String> = keysends.iter().filter_map(|k| k.id.clone()).collect();
+        let amounts: Vec<Option<u64>> = keysends.iter().map(|k| Some(k.amount)).collect();
+        let params: Value = json!({ "keysends": keysends });
+
+        let mut responses = self
+            .multi_pay("multi_pay_keysend", params, &ids, &amounts)
+            .await?;
+        let mut results: HashMap<String, MultiPayKeysendResult> = HashMap::with_capacity(ids.len());
+
+        for (id, amount) in ids.into_iter().zip(amounts.into_iter()) {
+            let result = match responses.remove(&id) {
+                Some(Ok(response)) => {
+                    match serde_json::from_value::<nip47::PayKeysendResponse>(response) {
+                        Ok(response) => {
+                            self.guard
+                                .spend(response.fees_paid.unwrap_or_default())
+                                .await;
+                            MultiPayKeysendResult::Paid {
+                                response: response.into(),
+                            }
+                        }
+                        // The wallet may have paid: keep the reservation
+                        Err(e) => MultiPayKeysendResult::Failed {
+                            error: malformed(e).into(),
+                        },
+                    }
+                }
+                Some(Err(error)) => {
+                    self.guard.release(amount.unwrap_or_default()).await;
+                    MultiPayKeysendResult::Failed {
+                        error: error.into(),
+                    }
+                }
+                // On timeout the wallet may have paid: keep the reservation
+                None => MultiPayKeysendResult::Failed {
+                    error: no_response().into(),
+                },
+            };
+            results.insert(id, result);
+        }
+
+        Ok(results)
     }
 
     /// Create invoice
     pub async fn make_invoice(&self, params: MakeInvoiceRequest) -> Result<MakeInvoiceResponse> {
+        self.guard.check_method(nip47::Method::MakeInvoice)?;
         Ok(self.inner.make_invoice(params.into()).await?.into())
     }
 
@@ -74,6 +426,7 @@ impl NostrWalletConnect {
         &self,
         params: LookupInvoiceRequest,
     ) -> Result<LookupInvoiceResponse> {
+        self.guard.check_method(nip47::Method::LookupInvoice)?;
         Ok(self.inner.lookup_invoice(params.into()).await?.into())
     }
 
@@ -82,17 +435,242 @@ impl NostrWalletConnect {
         &self,
         params: ListTransactionsRequest,
     ) -> Result<Vec<LookupInvoiceResponse>> {
+        self.guard.check_method(nip47::Method::ListTransactions)?;
         let list = self.inner.list_transactions(params.into()).await?;
         Ok(list.into_iter().map(|l| l.into()).collect())
     }
 
     /// Get balance
     pub async fn get_balance(&self) -> Result<GetBalanceResponse> {
+        self.guard.check_method(nip47::Method::GetBalance)?;
         Ok(self.inner.get_balance().await?.into())
     }
 
     /// Get info
     pub async fn get_info(&self) -> Result<GetInfoResponse> {
+        self.guard.check_method(nip47::Method::GetInfo)?;
         Ok(self.inner.get_info().await?.into())
     }
+
+    /// Create hold invoice
+    pub async fn make_hold_invoice(
+        &self,
+        params: MakeHoldInvoiceRequest,
+    ) -> Result<MakeHoldInvoiceResponse> {
+        self.guard.check_method(nip47::Method::MakeHoldInvoice)?;
+        Ok(self.inner.make_hold_invoice(params.into()).await?.into())
+    }
+
+    /// Cancel hold invoice
+    pub async fn cancel_hold_invoice(
+        &self,
+        params: CancelHoldInvoiceRequest,
+    ) -> Result<CancelHoldInvoiceResponse> {
+        self.guard.check_method(nip47::Method::CancelHoldInvoice)?;
+        Ok(self.inner.cancel_hold_invoice(params.into()).await?.into())
+    }
+
+    /// Settle hold invoice
+    pub async fn settle_hold_invoice(
+        &self,
+        params: SettleHoldInvoiceRequest,
+    ) -> Result<SettleHoldInvoiceResponse> {
+        self.guard.check_method(nip47::Method::SettleHoldInvoice)?;
+        Ok(self.inner.settle_hold_invoice(params.into()).await?.into())
+    }
+
+    /// Subscribe to wallet notifications
+    ///
+    /// Both NIP-04 (kind `23196`) and NIP-44 (kind `23197`) notifications are received.
+    /// Notifications that can't be decrypted or have an unknown type are skipped.
+    ///
+    /// The stream terminates when the client shutdowns.
+    pub async fn notifications(&self) -> Result<NostrWalletConnectNotificationStream> {
+        self.connect_relays().await?;
+
+        let nwc_client = self.inner.client();
+
+        let app: nostr::Keys = nostr::Keys::new(self.uri.secret.clone());
+        let wallet: nostr::PublicKey = self.uri.public_key;
+
+        let filter: nostr::Filter = nostr::Filter::new()
+            .author(wallet)
+            .pubkey(app.public_key())
+            .kinds([
+                nostr::Kind::WalletConnectNotification,
+                nostr::Kind::from_u16(NIP44_NOTIFICATION_KIND),
+            ])
+            .since(nostr::Timestamp::now());
+
+        // Get the notifications channel before subscribing, to not miss any event
+        let notifications = nwc_client.notifications();
+
+        nwc_client
+            .subscribe(client::ReqTarget::auto([filter]))
+            .await?;
+
+        let stream = notifications
+            .take_while(|n| future::ready(!matches!(n, client::ClientNotification::Shutdown)))
+            .filter_map(move |notification| {
+                let notification = match notification {
+                    client::ClientNotification::Event { event, .. } => {
+                        decrypt_notification(&app, &wallet, &event)
+                    }
+                    _ => None,
+                };
+                future::ready(notification)
+            });
+
+        Ok(NostrWalletConnectNotificationStream {
+            stream: Mutex::new(Box::pin(stream)),
+        })
+    }
+}
+
+fn decrypt_notification(
+    app: &nostr::Keys,
+    wallet: &nostr::PublicKey,
+    event: &nostr::Event,
+) -> Option<WalletNotification> {
+    if event.pubkey != *wallet {
+        return None;
+    }
+
+    let content: String = if event.kind == nostr::Kind::WalletConnectNotification {
+        nip04::decrypt(app.secret_key(), wallet, &event.content).ok()?
+    } else if event.kind.as_u16() == NIP44_NOTIFICATION_KIND {
+        nip44::decrypt(app.secret_key(), wallet, &event.content).ok()?
+    } else {
+        return None;
+    };
+
+    WalletNotification::from_json(&content).ok().flatten()
+}
+
+/// Parse a multi-pay response event, returning the item ID and the result
+fn parse_multi_pay_response(
+    app: &nostr::Keys,
+    wallet: &nostr::PublicKey,
+    event: &nostr::Event,
+) -> Option<(String, Result<Value, nip47::NIP47Error>)> {
+    let id: String = event.tags.identifier()?.to_string();
+    let content: String = if uses_nip44(event) {
+        nip44::decrypt(app.secret_key(), wallet, &event.content).ok()?
+    } else {
+        nip04::decrypt(app.secret_key(), wallet, &event.content).ok()?
+    };
+    let mut response: Value = serde_json::from_str(&content).ok()?;
+
+    match response.get_mut("error").map(Value::take) {
+        Some(error) if !error.is_null() => {
+            let error: nip47::NIP47Error = serde_json::from_value(error).ok()?;
+            Some((id, Err(error)))
+        }
+        _ => {
+            let result: Value = response
+                .get_mut("result")
+                .map(Value::take)
+                .unwrap_or_default();
+            Some((id, Ok(result)))
+        }
+    }
+}
+
+/// Check if the event is encrypted with NIP-44
+///
+/// Events without the `encryption` tag are encrypted with NIP-04.
+fn uses_nip44(event: &nostr::Event) -> bool {
+    event.tags.iter().any(|tag| match tag.as_slice() {
+        [kind, scheme, ..] => kind == ENCRYPTION_TAG && scheme == NIP44_V2,
+        _ => false,
+    })
+}
+
+/// Check if the wallet info event advertises NIP-44 in its `encryption` tag
+///
+/// The tag value is a space-separated list of schemes.
+fn advertises_nip44(info: &nostr::Event) -> bool {
+    info.tags.iter().any(|tag| match tag.as_slice() {
+        [kind, schemes, ..] => {
+            kind == ENCRYPTION_TAG && schemes.split_whitespace().any(|s| s == NIP44_V2)
+        }
+        _ => false,
+    })
+}
+
+/// Random ID for the multi-pay items without one
+fn random_id() -> String {
+    nostr::SubscriptionId::generate().to_string()
+}
+
+/// Get the amount to pay for an invoice, in millisats
+///
+/// The amount of the request is used only for amountless invoices.
+/// Return `None` if the invoice can't be decoded.
+fn invoice_amount(params: &nip47::PayInvoiceRequest) -> Option<u64> {
+    Bolt11Invoice::decode(&params.invoice)?
+        .amount_msat
+        .or(params.amount)
+}
+
+/// Check if the error is an error response of the wallet
+///
+/// Only then the payment surely failed.
+fn is_wallet_error(e: &nwc::error::Error) -> bool {
+    matches!(e, nwc::error::Error::NIP47(nip47::Error::ErrorCode(..)))
+}
+
+fn no_response() -> nip47::NIP47Error {
+    nip47::NIP47Error {
+        code: nip47::ErrorCode::Other,
+        message: String::from("No response from the wallet before the timeout"),
+    }
+}
+
+fn malformed(e: serde_json::Error) -> nip47::NIP47Error {
+    nip47::NIP47Error {
+        code: nip47::ErrorCode::Other,
+        message: format!("Malformed response: {e}"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// BOLT11 spec vector without amount
+    const AMOUNTLESS: &str = "lnbc1pvjluezpp5qqqsyqcyq5rqwzqfqqqsyqcyq5rqwzqfqqqsyqcyq5rqwzqfqypqdpl2pkx2ctnv5sxxmmwwd5kgetjypeh2ursdae8g6twvus8g6rfwvs8qun0dfjkxaq8rkx3yf5tcsyz3d73gafnh3cax9rn449d9p5uxz9ezhhypd0elx87sjle52x86fux2ypatgddc6k63n7erqz25le42c4u4ecky03ylcqca784w";
+    /// BOLT11 spec vector of 2500 uBTC
+    const WITH_AMOUNT: &str = "lnbc2500u1pvjluezpp5qqqsyqcyq5rqwzqfqqqsyqcyq5rqwzqfqqqsyqcyq5rqwzqfqypqdq5xysxxatsyp3k7enxv4jsxqzpuaztrnwngzn3kdzw5hydlzf03qdgm2hdq27cqv3agm2awhz5se903vruatfhq77w3ls4evs3ch9zw97j25emudupq63nyw24cg27h2rspfj9srp";
+
+    fn request(invoice: &str, amount: Option<u64>) -> nip47::PayInvoiceRequest {
+        PayInvoiceRequest {
+            id: None,
+            invoice: invoice.to_string(),
+            amount,
+        }
+        .into()
+    }
+
+    #[test]
+    fn test_invoice_amount_prefers_decoded_amount() {
+        // A lower amount in the request must not bypass the limits
+        let params = request(WITH_AMOUNT, Some(1_000));
+        assert_eq!(invoice_amount(&params), Some(250_000_000));
+    }
+
+    #[test]
+    fn test_invoice_amount_amountless() {
+        let params = request(AMOUNTLESS, Some(1_000));
+        assert_eq!(invoice_amount(&params), Some(1_000));
+
+        let params = request(AMOUNTLESS, None);
+        assert_eq!(invoice_amount(&params), None);
+    }
+
+    #[test]
+    fn test_invoice_amount_invalid_invoice() {
+        let params = request("lnbc1invalid", Some(1_000));
+        assert_eq!(invoice_amount(&params), None);
+    }
 }
@@ -0,0 +1,460 @@
+// Copyright (c) 2023-2025 Rust Nostr Developers
+// Distributed under the MIT software license
+
+use std::collections::HashSet;
+use std::fmt;
+use std::ops::Deref;
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_utility::task;
+use futures_util::StreamExt;
+use nostr::nips::{nip04, nip44, nip47};
+use nostr::{JsonUtil, serde_json};
+use nostr_sdk::client;
+use tokio::sync::{Mutex, RwLock};
+use uniffi::{Error, Object};
+
+use super::{ENCRYPTION_TAG, NIP04, NIP44_V2, uses_nip44};
+use crate::error::Result;
+use crate::protocol::key::{Keys, PublicKey, SecretKey};
+use crate::protocol::nips::nip47::{
+    CancelHoldInvoiceRequest, CancelHoldInvoiceResponse, ErrorCode, GetBalanceResponse,
+    GetInfoResponse, ListTransactionsRequest, LookupInvoiceRequest, LookupInvoiceResponse,
+    MakeHoldInvoiceRequest, MakeHoldInvoiceResponse, MakeInvoiceRequest, MakeInvoiceResponse,
+    Method, NostrWalletConnectUri, PayInvoiceRequest, PayInvoiceResponse, PayKeysendRequest,
+    PayKeysendResponse, SettleHoldInvoiceRequest, SettleHoldInvoiceResponse,
+};
+use crate::protocol::types::RelayUrl;
+
+/// Max clock skew accepted for the requests sent before the service started
+const MAX_CLOCK_SKEW: Duration = Duration::from_secs(60);
+
+/// Wallet backend error
+///
+/// The code and the message are sent back to the app.
+// DON'T USE '#[uniffi(flat_error)]' here,
+// as it will cause the following panic in callbacks: "Can't lift flat errors"
+#[derive(Debug, Error)]
+pub enum WalletBackendError {
+    Nip47 { code: ErrorCode, message: String },
+}
+
+impl fmt::Display for WalletBackendError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Nip47 { code, message } => write!(f, "{code:?}: {message}"),
+        }
+    }
+}
+
+impl From<uniffi::UnexpectedUniFFICallbackError> for WalletBackendError {
+    fn from(e: uniffi::UnexpectedUniFFICallbackError) -> Self {
+        Self::Nip47 {
+            code: ErrorCode::Internal,
+            message: e.reason,
+        }
+    }
+}
+
+impl From<WalletBackendError> for nip47::NIP47Error {
+    fn from(e: WalletBackendError) -> Self {
+        match e {
+            WalletBackendError::Nip47 { code, message } => Self {
+                code: code.into(),
+                message,
+            },
+        }
+    }
+}
+
+/// Wallet backend
+///
+/// Execute the requests received by the [`NostrWalletConnectService`].
+#[uniffi::export(with_foreign)]
+#[async_trait::async_trait]
+pub trait WalletBackend: Send + Sync {
+    async fn pay_invoice(
+        &self,
+        params: PayInvoiceRequest,
+    ) -> Result<PayInvoiceResponse, WalletBackendError>;
+
+    async fn pay_keysend(
+        &self,
+        params: PayKeysendRequest,
+    ) -> Result<PayKeysendResponse, WalletBackendError>;
+
+    async fn make_invoice(
+        &self,
+        params: MakeInvoiceRequest,
+    ) -> Result<MakeInvoiceResponse, WalletBackendError>;
+
+    async fn lookup_invoice(
+        &self,
+        params: LookupInvoiceRequest,
+    ) -> Result<LookupInvoiceResponse, WalletBackendError>;
+
+    async fn list_transactions(
+        &self,
+        params: ListTransactionsRequest,
+    ) -> Result<Vec<LookupInvoiceResponse>, WalletBackendError>;
+
+    async fn get_balance(&self) -> Result<GetBalanceResponse, WalletBackendError>;
+
+    async fn get_info(&self) -> Result<GetInfoResponse, WalletBackendError>;
+
+    async fn make_hold_invoice(
+        &self,
+        params: MakeHoldInvoiceRequest,
+    ) -> Result<MakeHoldInvoiceResponse, WalletBackendError>;
+
+    async fn cancel_hold_invoice(
+        &self,
+        params: CancelHoldInvoiceRequest,
+    ) -> Result<CancelHoldInvoiceResponse, WalletBackendError>;
+
+    async fn settle_hold_invoice(
+        &self,
+        params: SettleHoldInvoiceRequest,
+    ) -> Result<SettleHoldInvoiceResponse, WalletBackendError>;
+}
+
+/// Nostr Wallet Connect service
+///
+/// Serve the NWC requests (kind `23194`) of the authorized apps, using a [`WalletBackend`].
+///
+/// <https://github.com/nostr-protocol/nips/blob/master/47.md>
+#[derive(Object)]
+pub struct NostrWalletConnectService {
+    client: client::Client,
+    keys: nostr::Keys,
+    relays: Vec<nostr::RelayUrl>,
+    methods: Vec<nip47::Method>,
+    apps: RwLock<HashSet<nostr::PublicKey>>,
+    /// Requests already handled, to not execute them twice if re-delivered
+    handled: Mutex<HashSet<nostr::EventId>>,
+}
+
+#[uniffi::export(async_runtime = "tokio")]
+impl NostrWalletConnectService {
+    /// Construct a new wallet service
+    ///
+    /// `methods` are the methods advertised in the info event (kind `13194`) and accepted by the service.
+    /// If empty, all the methods are enabled.
+    #[uniffi::constructor(default(methods = []))]
+    pub fn new(keys: &Keys, relays: Vec<Arc<RelayUrl>>, methods: Vec<Method>) -> Self {
+        let methods: Vec<nip47::Method> = if methods.is_empty() {
+            vec![
+                nip47::Method::PayInvoice,
+                nip47::Method::PayKeysend,
+                nip47::Method::MakeInvoice,
+                nip47::Method::LookupInvoice,
+                nip47::Method::ListTransactions,
+                nip47::Method::GetBalance,
+                nip47::Method::GetInfo,
+                nip47::Method::MakeHoldInvoice,
+                nip47::Method::CancelHoldInvoice,
+                nip47::Method::SettleHoldInvoice,
+            ]
+        } else {
+            methods.into_iter().map(|m| m.into()).collect()
+        };
+
+        Self {
+            client: client::Client::default(),
+            keys: keys.deref().clone(),
+            relays: relays
+                .into_iter()
+                .map(|u| u.as_ref().deref().clone())
+                .collect(),
+            methods,
+            apps: RwLock::new(HashSet::new()),
+            handled: Mutex::new(HashSet::new()),
+        }
+    }
+
+    /// Get wallet service public key
+    pub fn public_key(&self) -> PublicKey {
+        self.keys.public_key().into()
+    }
+
+    /// Get wallet service relays
+    pub fn relays(&self) -> Vec<Arc<RelayUrl>> {
+        self.relays
+            .iter()
+            .cloned()
+            .map(|u| Arc::new(u.into()))
+            .collect()
+    }
+
+    /// Compose the connection URI to share with an app
+    ///
+    /// The app is NOT automatically authorized: use [`NostrWalletConnectService::authorize`].
+    #[uniffi::method(default(lud16 = None))]
+    pub fn uri(&self, app_secret: &SecretKey, lud16: Option<String>) -> NostrWalletConnectUri {
+        nip47::NostrWalletConnectUri::new(
+            self.keys.public_key(),
+            self.relays.clone(),
+            app_secret.deref().clone(),
+            lud16,
+        )
+        .into()
+    }
+
+    /// Authorize an app to send requests
+    ///
+    /// The public key is the one derived from the secret of the connection URI.
+    pub async fn authorize(&self, app_public_key: &PublicKey) {
+        let mut apps = self.apps.write().await;
+        apps.insert(**app_public_key);
+    }
+
+    /// Revoke the authorization of an app
+    ///
+    /// Return `false` if the app wasn't authorized.
+    pub async fn revoke(&self, app_public_key: &PublicKey) -> bool {
+        let mut apps = self.apps.write().await;
+        apps.remove(app_public_key.deref())
+    }
+
+    /// Connect to relays, publish the info event and serve requests
+    ///
+    /// Requests from apps that aren't authorized are answered with an `UNAUTHORIZED` error.
+    /// Each request is handled in its own task, so a slow payment doesn't block the other apps.
+    ///
+    /// Requests created before the service started (minus a small clock skew)
+    /// or already handled are ignored, to not execute a payment twice if a relay re-delivers them.
+    ///
+    /// This method returns only when the service is stopped.
+    pub async fn serve(self: Arc<Self>, backend: Arc<dyn WalletBackend>) -> Result<()> {
+        for url in self.relays.iter() {
+            if self.client.relay(url).await?.is_none() {
+                self.client.add_relay(url).await?;
+            }
+        }
+
+        self.client.connect().await;
+
+        // Publish info event
+        let info: nostr::Event = self.info_event()?;
+        self.client.send_event(&info).await?;
+
+        let mut notifications = self.client.notifications();
+
+        let since: nostr::Timestamp = nostr::Timestamp::now() - MAX_CLOCK_SKEW;
+        let filter: nostr::Filter = nostr::Filter::new()
+            .kind(nostr::Kind::WalletConnectRequest)
+            .pubkey(self.keys.public_key())
+            .since(since);
+        self.client
+            .subscribe(client::ReqTarget::auto([filter]))
+            .await?;
+
+        while let Some(notification) = notifications.next().await {
+            match notification {
+                client::ClientNotification::Event { event, .. } => {
+                    if event.kind != nostr::Kind::WalletConnectRequest {
+                        continue;
+                    }
+
+                    // Don't trust the relay to honour `since`
+                    if event.created_at < since {
+                        continue;
+                    }
+
+                    // Skip expired requests (NIP-40)
+                    if event.is_expired() {
+                        continue;
+                    }
+
+                    // Skip the requests already handled
+                    if !self.handled.lock().await.insert(event.id) {
+                        continue;
+                    }
+
+                    let service: Arc<Self> = self.clone();
+                    let backend: Arc<dyn WalletBackend> = backend.clone();
+
+                    task::spawn(async move {
+                        // Skip requests that can't be decrypted
+                        if let Ok(response) = service.handle_request(&event, &backend).await {
+                            if let Err(e) = service.client.send_event(&response).await {
+                                tracing::warn!(id = %event.id, error = %e, "Can't send NWC response");
+                            }
+                        }
+                    });
+                }
+                client::ClientNotification::Message { .. } => {}
+                client::ClientNotification::Shutdown => break,
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Stop the service and disconnect from relays
+    pub async fn stop(&self) {
+        self.client.shutdown().await
+    }
+}
+
+impl NostrWalletConnectService {
+    fn info_event(&self) -> Result<nostr::Event> {
+        let methods: Vec<String> = self.methods.iter().filter_map(method_name).collect();
+        let event = nostr::EventBuilder::new(nostr::Kind::WalletConnectInfo, methods.join(" "))
+            .tag(nostr::Tag::custom(
+                ENCRYPTION_TAG,
+                [format!("{NIP44_V2} {NIP04}")].as_slice(),
+            ))
+            .finalize(&self.keys)?;
+        Ok(event)
+    }
+
+    async fn handle_request(
+        &self,
+        event: &nostr::Event,
+        backend: &Arc<dyn WalletBackend>,
+    ) -> Result<nostr::Event> {
+        let use_nip44: bool = uses_nip44(event);
+
+        let content: String = if use_nip44 {
+            nip44::decrypt(self.keys.secret_key(), &event.pubkey, &event.content)?
+        } else {
+            nip04::decrypt(self.keys.secret_key(), &event.pubkey, &event.content)?
+        };
+
+        let response: nip47::Response = match nip47::Request::from_json(&content) {
+            Ok(request) => {
+                let method: nip47::Method = request.method.clone();
+
+                match self.check(&event.pubkey, &method).await {
+                    Ok(()) => match dispatch(backend, request.params).await {
+                        Ok(result) => nip47::Response {
+                            result_type: method,
+                            error: None,
+                            result: Some(result),
+                        },
+                        Err(e) => nip47::Response {
+                            result_type: method,
+                            error: Some(e.into()),
+                            result: None,
+                        },
+                    },
+                    Err(error) => nip47::Response {
+                        result_type: method,
+                        error: Some(error),
+                        result: None,
+                    },
+                }
+            }
+            Err(e) => invalid_request_response(&content, e),
+        };
+
+        let content: String = if use_nip44 {
+            nip44::encrypt(
+                self.keys.secret_key(),
+                &event.pubkey,
+                response.as_json(),
+                nip44::Version::V2,
+            )?
+        } else {
+            nip04::encrypt(self.keys.secret_key(), &event.pubkey, response.as_json())?
+        };
+
+        let mut tags: Vec<nostr::Tag> = vec![
+            nostr::Tag::public_key(event.pubkey),
+            nostr::Tag::event(event.id),
+        ];
+
+        if use_nip44 {
+            tags.push(nostr::Tag::custom(
+                ENCRYPTION_TAG,
+                [NIP44_V2.to_string()].as_slice(),
+            ));
+        }
+
+        let event = nostr::EventBuilder::new(nostr::Kind::WalletConnectResponse, content)
+            .tags(tags)
+            .finalize(&self.keys)?;
+        Ok(event)
+    }
+
+    /// Check if the app is authorized and the method enabled
+    async fn check(
+        &self,
+        app: &nostr::PublicKey,
+        method: &nip47::Method,
+    ) -> Result<(), nip47::NIP47Error> {
+        let apps = self.apps.read().await;
+
+        if !apps.contains(app) {
+            return Err(nip47::NIP47Error {
+                code: nip47::ErrorCode::Unauthorized,
+                message: String::from("App not authorized"),
+            });
+        }
+
+        if matches!(method, nip47::Method::Unknown(..)) || !self.methods.contains(method) {
+            return Err(nip47::NIP47Error {
+                code: nip47::ErrorCode::NotImplemented,
+                message: String::from("Method not supported"),
+            });
+        }
+
+        Ok(())
+    }
+}
+
+fn method_name(method: &nip47::Method) -> Option<String> {
+    match serde_json::to_value(method).ok()? {
+        serde_json::Value::String(name) => Some(name),
+        _ => None,
+    }
+}
+
+async fn dispatch(
+    backend: &Arc<dyn WalletBackend>,
+    params: nip47::RequestParams,
+) -> Result<nip47::ResponseResult, WalletBackendError> {
+    Ok(match params {
+        nip47::RequestParams::PayInvoice(params) => {
+            nip47::ResponseResult::PayInvoice(backend.pay_invoice(params.into()).await?.into())
+        }
+        nip47::RequestParams::PayKeysend(params) => {
+            nip47::ResponseResult::PayKeysend(backend.pay_keysend(params.into()).await?.into())
+        }
+        nip47::RequestParams::MakeInvoice(params) => {
+            nip47::ResponseResult::MakeInvoice(backend.make_invoice(params.into()).await?.into())
+        }
+        nip47::RequestParams::LookupInvoice(params) => nip47::ResponseResult::LookupInvoice(
+            backend.lookup_invoice(params.into()).await?.into(),
+        ),
+        nip47::RequestParams::ListTransactions(params) => nip47::ResponseResult::ListTransactions(
+            backend
+                .list_transactions(params.into())
+                .await?
+                .into_iter()
+                .map(|t| t.into())
+                .collect(),
+        ),
+        nip47::RequestParams::GetBalance => {
+            nip47::ResponseResult::GetBalance(backend.get_balance().await?.into())
+        }
+        nip47::RequestParams::GetInfo => {
+            nip47::ResponseResult::GetInfo(backend.get_info().await?.into())
+        }
+        nip47::RequestParams::MakeHoldInvoice(params) => nip47::ResponseResult::MakeHoldInvoice(
+            backend.make_hold_invoice(params.into()).await?.into(),
+        ),
+        nip47::RequestParams::CancelHoldInvoice(params) => {
+            nip47::ResponseResult::CancelHoldInvoice(
+                backend.cancel_hold_invoice(params.into()).await?.into(),
+            )
+        }
+        nip47::RequestParams::SettleHoldInvoice(params) => {
+            nip47::ResponseResult::SettleHoldInvoice(
+                backend.settle_hold_invoice(params.into()).await?.into(),
+            )
+        }
+    })
+}
@@ -19,7 +19,9 @@ use crate::protocol::nips::nip01::Metadata;
 use crate::protocol::nips::nip09::EventDeletionRequest;
 use crate::protocol::nips::nip22::CommentTarget;
 use crate::protocol::nips::nip34::{GitIssue, GitPatch, GitRepositoryAnnouncement};
+use crate::protocol::nips::nip57::ZapRequestData;
 use crate::protocol::nips::nip65::RelayMetadata;
+use crate::protocol::nips::nip94::FileMetadata;
 use crate::protocol::signer::{
     AsyncNostrSigner, IntermediateAsyncNostrSigner, IntermediateNostrSigner, NostrSigner,
 };
@@ -358,6 +360,37 @@ impl EventBuilder {
         })
     }
 
+    /// Public zap request
+    ///
+    /// <https://github.com/nostr-protocol/nips/blob/master/57.md>
+    #[uniffi::constructor]
+    pub fn public_zap_request(data: &ZapRequestData) -> Self {
+        Self {
+            inner: nostr::EventBuilder::public_zap_request(data.deref().clone()),
+        }
+    }
+
+    /// Zap receipt
+    ///
+    /// <https://github.com/nostr-protocol/nips/blob/master/57.md>
+    #[uniffi::constructor]
+    pub fn zap_receipt(bolt11: &str, preimage: Option<String>, zap_request: &Event) -> Self {
+        Self {
+            inner: nostr::EventBuilder::zap_receipt(bolt11, preimage, zap_request.deref()),
+        }
+    }
+
+    /// File metadata
+    ///
+    /// <https://github.com/nostr-protocol/nips/blob/master/94.md>
+    #[uniffi::constructor]
+    pub fn file_metadata(description: &str, metadata: &FileMetadata) -> Self {
+        Self {
+            inner: nostr::EventBuilder::new(nostr::Kind::FileMetadata, description)
+                .tags(metadata.to_tags()),
+        }
+    }
+
     /// Private direct message relay list
     ///
     /// <https://github.com/nostr-protocol/nips/blob/master/17.md>
@@ -8,10 +8,11 @@ use std::sync::Arc;
 use nostr::event;
 use uniffi::Object;
 
-use super::Tag;
+use super::{Tag, TagKind, TagStandard};
 use crate::error::Result;
 use crate::protocol::event::{EventId, PublicKey, Timestamp};
 use crate::protocol::nips::nip01::Coordinate;
+use crate::protocol::nips::nip94::FileMetadata;
 
 #[derive(Debug, PartialEq, Eq, Hash, Object)]
 #[uniffi::export(Debug, Eq, Hash)]
@@ -88,6 +89,18 @@ impl Tags {
             .collect()
     }
 
+    /// Get all the standardized tags of a specific kind
+    ///
+    /// Tags that can't be standardized are skipped.
+    pub fn filter_standardized(&self, kind: TagKind) -> Vec<TagStandard> {
+        let kind: String = kind.to_string();
+        self.inner
+            .iter()
+            .filter(|t| t.as_slice().first() == Some(&kind))
+            .filter_map(|t| TagStandard::parse(t.as_slice()))
+            .collect()
+    }
+
     /// Extract identifier (`d` tag), if exists.
     pub fn identifier(&self) -> Option<String> {
         self.inner.identifier().map(|i| i.to_string())
@@ -125,4 +138,18 @@ impl Tags {
     pub fn hashtags(&self) -> Vec<String> {
         self.inner.hashtags().map(|t| t.to_owned()).collect()
     }
+
+    /// Extract media metadata from `imeta` tags.
+    ///
+    /// <https://github.com/nostr-protocol/nips/blob/master/92.md>
+    pub fn imeta(&self) -> Vec<Arc<FileMetadata>> {
+        self.inner
+            .iter()
+            .filter_map(|t| match t.as_slice().split_first() {
+                Some((kind, values)) if kind == "imeta" => FileMetadata::from_imeta_values(values),
+                _ => None,
+            })
+            .map(Arc::new)
+            .collect()
+    }
 }
@@ -10,12 +10,15 @@ use nostr::prelude::{Nip89Tag, TagCodec};
 use uniffi::Object;
 
 pub mod list;
+pub mod standard;
 
 pub use self::list::Tags;
+pub use self::standard::{TagKind, TagStandard};
 use crate::error::Result;
 use crate::protocol::event::{EventId, PublicKey};
 use crate::protocol::filter::SingleLetterTag;
 use crate::protocol::nips::nip01::Coordinate;
+use crate::protocol::nips::nip94::FileMetadata;
 use crate::protocol::types::{RelayUrl, Timestamp};
 
 /// Tag
@@ -66,10 +69,12 @@ impl Tag {
         self.inner.single_letter_tag().map(|s| Arc::new(s.into()))
     }
 
-    // /// Get standardized tag
-    // pub fn as_standardized(&self) -> Option<TagStandard> {
-    //     self.inner.as_standardized().cloned().map(|t| t.into())
-    // }
+    /// Get standardized tag
+    ///
+    /// Return `None` if the tag is unknown or malformed.
+    pub fn as_standardized(&self) -> Option<TagStandard> {
+        TagStandard::parse(self.inner.as_slice())
+    }
 
     /// Get tag len
     pub fn len(&self) -> u64 {
@@ -81,6 +86,12 @@ impl Tag {
         self.inner.as_slice().to_vec()
     }
 
+    /// Compose tag from a standardized tag
+    #[uniffi::constructor]
+    pub fn from_standardized(tag: TagStandard) -> Self {
+        Self { inner: tag.into() }
+    }
+
     /// Compose `["e", "<event-id">]`
     ///
     /// <https://github.com/nostr-protocol/nips/blob/master/01.md>
@@ -166,6 +177,16 @@ impl Tag {
         }
     }
 
+    /// Compose `["imeta", "url <url>", "m <mime-type>", ...]` tag
+    ///
+    /// <https://github.com/nostr-protocol/nips/blob/master/92.md>
+    #[uniffi::constructor]
+    pub fn imeta(metadata: &FileMetadata) -> Self {
+        Self {
+            inner: event::Tag::custom("imeta", metadata.to_imeta_values().as_slice()),
+        }
+    }
+
     /// Protected event
     ///
     /// JSON: `["-"]`
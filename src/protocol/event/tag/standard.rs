@@ -0,0 +1,850 @@
+// Copyright (c) 2022-2023 Yuki Kishimoto
+// Copyright (c) 2023-2025 Rust Nostr Developers
+// Distributed under the MIT software license
+
+use std::fmt;
+use std::str::FromStr;
+use std::sync::Arc;
+
+use nostr::event;
+use nostr::nips::nip01;
+use uniffi::Enum;
+
+use crate::protocol::event::{EventId, Kind};
+use crate::protocol::filter::SingleLetterTag;
+use crate::protocol::key::PublicKey;
+use crate::protocol::nips::nip01::Coordinate;
+use crate::protocol::nips::nip10::Marker;
+use crate::protocol::nips::nip65::RelayMetadata;
+use crate::protocol::nips::nip94::FileMetadata;
+use crate::protocol::types::{ImageDimensions, RelayUrl, Timestamp};
+
+/// Standardized tag
+///
+/// Typed representation of the tags defined by the NIPs.
+/// Use [`Tag::as_standardized`] to parse a tag and [`Tag::from_standardized`] to compose it.
+#[derive(Enum)]
+pub enum TagStandard {
+    /// Event tag (`e` or `E`)
+    ///
+    /// The uppercase `E` is the NIP-22 root event tag, which has no marker.
+    ///
+    /// <https://github.com/nostr-protocol/nips/blob/master/01.md>, <https://github.com/nostr-protocol/nips/blob/master/10.md> and <https://github.com/nostr-protocol/nips/blob/master/22.md>
+    EventTag {
+        event_id: Arc<EventId>,
+        relay_url: Option<Arc<RelayUrl>>,
+        marker: Option<Marker>,
+        /// Should be the public key of the author of the referenced event
+        public_key: Option<Arc<PublicKey>>,
+        /// Whether the e tag is an uppercase E or not
+        uppercase: bool,
+    },
+    /// Quote tag (`q`)
+    ///
+    /// <https://github.com/nostr-protocol/nips/blob/master/18.md>
+    Quote {
+        event_id: Arc<EventId>,
+        relay_url: Option<Arc<RelayUrl>>,
+        /// Should be the public key of the author of the referenced event
+        public_key: Option<Arc<PublicKey>>,
+    },
+    /// Public key tag (`p` or `P`)
+    ///
+    /// <https://github.com/nostr-protocol/nips/blob/master/01.md>
+    PublicKeyTag {
+        public_key: Arc<PublicKey>,
+        relay_url: Option<Arc<RelayUrl>>,
+        alias: Option<String>,
+        /// Whether the p tag is an uppercase P or not
+        uppercase: bool,
+    },
+    /// Coordinate tag (`a` or `A`)
+    ///
+    /// <https://github.com/nostr-protocol/nips/blob/master/01.md>
+    CoordinateTag {
+        coordinate: Arc<Coordinate>,
+        relay_url: Option<Arc<RelayUrl>>,
+        /// Whether the a tag is an uppercase A or not
+        uppercase: bool,
+    },
+    /// Kind tag (`k` or `K`)
+    KindTag {
+        kind: Arc<Kind>,
+        /// Whether the k tag is an uppercase K or not
+        uppercase: bool,
+    },
+    /// Identifier tag (`d`)
+    Identifier { identifier: String },
+    /// Hashtag (`t`)
+    Hashtag { hashtag: String },
+    /// Geohash (`g`)
+    Geohash { geohash: String },
+    /// Reference (`r`)
+    Reference { reference: String },
+    /// Relay metadata (`r`)
+    ///
+    /// <https://github.com/nostr-protocol/nips/blob/master/65.md>
+    RelayMetadataTag {
+        relay_url: Arc<RelayUrl>,
+        metadata: Option<RelayMetadata>,
+    },
+    /// Relay (`relay`)
+    Relay { relay_url: Arc<RelayUrl> },
+    /// Relays (`relays`)
+    Relays { relay_urls: Vec<Arc<RelayUrl>> },
+    /// Proof of Work (`nonce`)
+    ///
+    /// <https://github.com/nostr-protocol/nips/blob/master/13.md>
+    Pow { nonce: String, difficulty: u8 },
+    /// Client (`client`)
+    ///
+    /// <https://github.com/nostr-protocol/nips/blob/master/89.md>
+    Client {
+        name: String,
+        address: Option<Arc<Coordinate>>,
+        relay_url: Option<Arc<RelayUrl>>,
+    },
+    /// Content warning (`content-warning`)
+    ///
+    /// <https://github.com/nostr-protocol/nips/blob/master/36.md>
+    ContentWarning { reason: Option<String> },
+    /// Expiration (`expiration`)
+    ///
+    /// <https://github.com/nostr-protocol/nips/blob/master/40.md>
+    Expiration { timestamp: Arc<Timestamp> },
+    /// Subject (`subject`)
+    Subject { subject: String },
+    /// Challenge (`challenge`)
+    ///
+    /// <https://github.com/nostr-protocol/nips/blob/master/42.md>
+    Challenge { challenge: String },
+    /// Title (`title`)
+    Title { title: String },
+    /// Image (`image`)
+    Image {
+        url: String,
+        dimensions: Option<ImageDimensions>,
+    },
+    /// Thumbnail (`thumb`)
+    Thumb {
+        url: String,
+        dimensions: Option<ImageDimensions>,
+    },
+    /// Summary (`summary`)
+    Summary { summary: String },
+    /// Published at (`published_at`)
+    PublishedAt { timestamp: Arc<Timestamp> },
+    /// Description (`description`)
+    Description { desc: String },
+    /// Bolt11 invoice (`bolt11`)
+    ///
+    /// <https://github.com/nostr-protocol/nips/blob/master/57.md>
+    Bolt11 { bolt11: String },
+    /// Preimage (`preimage`)
+    ///
+    /// <https://github.com/nostr-protocol/nips/blob/master/57.md>
+    Preimage { preimage: String },
+    /// Amount (`amount`)
+    ///
+    /// <https://github.com/nostr-protocol/nips/blob/master/57.md>
+    Amount {
+        millisats: u64,
+        bolt11: Option<String>,
+    },
+    /// LNURL (`lnurl`)
+    ///
+    /// <https://github.com/nostr-protocol/nips/blob/master/57.md>
+    Lnurl { lnurl: String },
+    /// Zap split (`zap`)
+    ///
+    /// <https://github.com/nostr-protocol/nips/blob/master/57.md>
+    Zap {
+        public_key: Arc<PublicKey>,
+        relay_url: Option<Arc<RelayUrl>>,
+        weight: Option<f64>,
+    },
+    /// Anonymous zap (`anon`)
+    ///
+    /// <https://github.com/nostr-protocol/nips/blob/master/57.md>
+    Anon { msg: Option<String> },
+    /// Name (`name`)
+    Name { name: String },
+    /// URL (`url`)
+    Url { url: String },
+    /// MIME type (`m`)
+    MimeType { mime: String },
+    /// SHA256 hash (`x`)
+    Sha256 { hash: String },
+    /// Size in bytes (`size`)
+    Size { size: u64 },
+    /// Dimensions (`dim`)
+    Dim { dimensions: ImageDimensions },
+    /// Magnet URI (`magnet`)
+    Magnet { uri: String },
+    /// Blurhash (`blurhash`)
+    Blurhash { blurhash: String },
+    /// Custom emoji (`emoji`)
+    ///
+    /// <https://github.com/nostr-protocol/nips/blob/master/30.md>
+    Emoji { shortcode: String, url: String },
+    /// Muted word (`word`)
+    ///
+    /// <https://github.com/nostr-protocol/nips/blob/master/51.md>
+    Word { word: String },
+    /// Label namespace (`L`)
+    ///
+    /// <https://github.com/nostr-protocol/nips/blob/master/32.md>
+    LabelNamespace { namespace: String },
+    /// Label (`l`)
+    ///
+    /// <https://github.com/nostr-protocol/nips/blob/master/32.md>
+    Label {
+        value: String,
+        namespace: Option<String>,
+    },
+    /// Inline media metadata (`imeta`)
+    ///
+    /// <https://github.com/nostr-protocol/nips/blob/master/92.md>
+    Imeta { metadata: Arc<FileMetadata> },
+    /// Alt (`alt`)
+    ///
+    /// <https://github.com/nostr-protocol/nips/blob/master/31.md>
+    Alt { summary: String },
+    /// Encrypted (`encrypted`)
+    Encrypted,
+    /// Protected event (`-`)
+    ///
+    /// <https://github.com/nostr-protocol/nips/blob/master/70.md>
+    Protected,
+}
+
+/// Tag kind
+#[derive(Enum)]
+pub enum TagKind {
+    /// Single letter tag (i.e., `e`, `p`, `t`, `E`)
+    SingleLetter { single_letter: Arc<SingleLetterTag> },
+    /// `relay`
+    Relay,
+    /// `relays`
+    Relays,
+    /// `nonce`
+    Nonce,
+    /// `client`
+    Client,
+    /// `content-warning`
+    ContentWarning,
+    /// `expiration`
+    Expiration,
+    /// `subject`
+    Subject,
+    /// `challenge`
+    Challenge,
+    /// `title`
+    Title,
+    /// `image`
+    Image,
+    /// `thumb`
+    Thumb,
+    /// `summary`
+    Summary,
+    /// `published_at`
+    PublishedAt,
+    /// `description`
+    Description,
+    /// `bolt11`
+    Bolt11,
+    /// `preimage`
+    Preimage,
+    /// `amount`
+    Amount,
+    /// `lnurl`
+    Lnurl,
+    /// `zap`
+    Zap,
+    /// `anon`
+    Anon,
+    /// `name`
+    Name,
+    /// `url`
+    Url,
+    /// `size`
+    Size,
+    /// `dim`
+    Dim,
+    /// `magnet`
+    Magnet,
+    /// `blurhash`
+    Blurhash,
+    /// `emoji`
+    Emoji,
+    /// `word`
+    Word,
+    /// `imeta`
+    Imeta,
+    /// `alt`
+    Alt,
+    /// `encrypted`
+    Encrypted,
+    /// `-`
+    Protected,
+    /// Custom
+    Custom { kind: String },
+}
+
+impl fmt::Display for TagKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let kind: &str = match self {
+            Self::SingleLetter { single_letter } => {
+                return write!(f, "{}", single_letter.as_char());
+            }
+            Self::Relay => "relay",
+            Self::Relays => "relays",
+            Self::Nonce => "nonce",
+            Self::Client => "client",
+            Self::ContentWarning => "content-warning",
+            Self::Expiration => "expiration",
+            Self::Subject => "subject",
+            Self::Challenge => "challenge",
+            Self::Title => "title",
+            Self::Image => "image",
+            Self::Thumb => "thumb",
+            Self::Summary => "summary",
+            Self::PublishedAt => "published_at",
+            Self::Description => "description",
+            Self::Bolt11 => "bolt11",
+            Self::Preimage => "preimage",
+            Self::Amount => "amount",
+            Self::Lnurl => "lnurl",
+            Self::Zap => "zap",
+            Self::Anon => "anon",
+            Self::Name => "name",
+            Self::Url => "url",
+            Self::Size => "size",
+            Self::Dim => "dim",
+            Self::Magnet => "magnet",
+            Self::Blurhash => "blurhash",
+            Self::Emoji => "emoji",
+            Self::Word => "word",
+            Self::Imeta => "imeta",
+            Self::Alt => "alt",
+            Self::Encrypted => "encrypted",
+            Self::Protected => "-",
+            Self::Custom { kind } => kind,
+        };
+        write!(f, "{kind}")
+    }
+}
+
+impl TagStandard {
+    /// Parse a standardized tag from its raw values
+    ///
+    /// Returns `None` if the tag is unknown or malformed.
+    pub(crate) fn parse(tag: &[String]) -> Option<Self> {
+        let (kind, values) = tag.split_first()?;
+
+        match kind.as_str() {
+            "e" => Some(Self::EventTag {
+                event_id: parse_event_id(values.first())?,
+                relay_url: parse_relay_url(values.get(1)),
+                marker: values.get(2).and_then(|m| parse_marker(m)),
+                public_key: parse_public_key(values.get(3)),
+                uppercase: false,
+            }),
+            // NIP-22 root event: no marker
+            "E" => Some(Self::EventTag {
+                event_id: parse_event_id(values.first())?,
+                relay_url: parse_relay_url(values.get(1)),
+                marker: None,
+                public_key: parse_public_key(values.get(2)),
+                uppercase: true,
+            }),
+            "q" => Some(Self::Quote {
+                event_id: parse_event_id(values.first())?,
+                relay_url: parse_relay_url(values.get(1)),
+                public_key: parse_public_key(values.get(2)),
+            }),
+            "p" | "P" => Some(Self::PublicKeyTag {
+                public_key: parse_public_key(values.first())?,
+                relay_url: parse_relay_url(values.get(1)),
+                alias: non_empty(values.get(2)),
+                uppercase: kind == "P",
+            }),
+            "a" | "A" => Some(Self::CoordinateTag {
+                coordinate: parse_coordinate(values.first())?,
+                relay_url: parse_relay_url(values.get(1)),
+                uppercase: kind == "A",
+            }),
+            "k" | "K" => {
+                let k: u16 = values.first()?.parse().ok()?;
+                Some(Self::KindTag {
+                    kind: Arc::new(nostr::Kind::from_u16(k).into()),
+                    uppercase: kind == "K",
+                })
+            }
+            "d" => Some(Self::Identifier {
+                identifier: values.first()?.clone(),
+            }),
+            "t" => Some(Self::Hashtag {
+                hashtag: values.first()?.clone(),
+            }),
+            "g" => Some(Self::Geohash {
+                geohash: values.first()?.clone(),
+            }),
+            "r" => {
+                let value: &String = values.first()?;
+                match nostr::RelayUrl::parse(value) {
+                    Ok(relay_url) => Some(Self::RelayMetadataTag {
+                        relay_url: Arc::new(relay_url.into()),
+                        metadata: values.get(1).and_then(|m| parse_relay_metadata(m)),
+                    }),
+                    Err(..) => Some(Self::Reference {
+                        reference: value.clone(),
+                    }),
+                }
+            }
+            "relay" => Some(Self::Relay {
+                relay_url: parse_relay_url(values.first())?,
+            }),
+            "relays" => Some(Self::Relays {
+                relay_urls: values
+                    .iter()
+                    .filter_map(|u| parse_relay_url(Some(u)))
+                    .collect(),
+            }),
+            "nonce" => Some(Self::Pow {
+                nonce: values.first()?.clone(),
+                difficulty: values.get(1)?.parse().ok()?,
+            }),
+            "client" => Some(Self::Client {
+                name: values.first()?.clone(),
+                address: parse_coordinate(values.get(1)),
+                relay_url: parse_relay_url(values.get(2)),
+            }),
+            "content-warning" => Some(Self::ContentWarning {
+                reason: non_empty(values.first()),
+            }),
+            "expiration" => Some(Self::Expiration {
+                timestamp: parse_timestamp(values.first())?,
+            }),
+            "subject" => Some(Self::Subject {
+                subject: values.first()?.clone(),
+            }),
+            "challenge" => Some(Self::Challenge {
+                challenge: values.first()?.clone(),
+            }),
+            "title" => Some(Self::Title {
+                title: values.first()?.clone(),
+            }),
+            "image" => Some(Self::Image {
+                url: values.first()?.clone(),
+                dimensions: values.get(1).and_then(|d| parse_dimensions(d)),
+            }),
+            "thumb" => Some(Self::Thumb {
+                url: values.first()?.clone(),
+                dimensions: values.get(1).and_then(|d| parse_dimensions(d)),
+            }),
+            "summary" => Some(Self::Summary {
+                summary: values.first()?.clone(),
+            }),
+            "published_at" => Some(Self::PublishedAt {
+                timestamp: parse_timestamp(values.first())?,
+            }),
+            "description" => Some(Self::Description {
+                desc: values.first()?.clone(),
+            }),
+            "bolt11" => Some(Self::Bolt11 {
+                bolt11: values.first()?.clone(),
+            }),
+            "preimage" => Some(Self::Preimage {
+                preimage: values.first()?.clone(),
+            }),
+            "amount" => Some(Self::Amount {
+                millisats: values.first()?.parse().ok()?,
+                bolt11: non_empty(values.get(1)),
+            }),
+            "lnurl" => Some(Self::Lnurl {
+                lnurl: values.first()?.clone(),
+            }),
+            "zap" => Some(Self::Zap {
+                public_key: parse_public_key(values.first())?,
+                relay_url: parse_relay_url(values.get(1)),
+                weight: values.get(2).and_then(|w| w.parse().ok()),
+            }),
+            "anon" => Some(Self::Anon {
+                msg: non_empty(values.first()),
+            }),
+            "name" => Some(Self::Name {
+                name: values.first()?.clone(),
+            }),
+            "url" => Some(Self::Url {
+                url: values.first()?.clone(),
+            }),
+            "m" => Some(Self::MimeType {
+                mime: values.first()?.clone(),
+            }),
+            "x" => Some(Self::Sha256 {
+                hash: values.first()?.clone(),
+            }),
+            "size" => Some(Self::Size {
+                size: values.first()?.parse().ok()?,
+            }),
+            "dim" => Some(Self::Dim {
+                dimensions: parse_dimensions(values.first()?)?,
+            }),
+            "magnet" => Some(Self::Magnet {
+                uri: values.first()?.clone(),
+            }),
+            "blurhash" => Some(Self::Blurhash {
+                blurhash: values.first()?.clone(),
+            }),
+            "emoji" => Some(Self::Emoji {
+                shortcode: values.first()?.clone(),
+                url: values.get(1)?.clone(),
+            }),
+            "word" => Some(Self::Word {
+                word: values.first()?.clone(),
+            }),
+            "L" => Some(Self::LabelNamespace {
+                namespace: values.first()?.clone(),
+            }),
+            "l" => Some(Self::Label {
+                value: values.first()?.clone(),
+                namespace: non_empty(values.get(1)),
+            }),
+            "imeta" => Some(Self::Imeta {
+                metadata: Arc::new(FileMetadata::from_imeta_values(values)?),
+            }),
+            "alt" => Some(Self::Alt {
+                summary: values.first()?.clone(),
+            }),
+            "encrypted" => Some(Self::Encrypted),
+            "-" => Some(Self::Protected),
+            _ => None,
+        }
+    }
+
+    /// Convert to the raw tag values
+    pub(crate) fn to_vec(self) -> Vec<String> {
+        match self {
+            Self::EventTag {
+                event_id,
+                relay_url,
+                marker,
+                public_key,
+                uppercase,
+            } => {
+                // NIP-22 root event: `["E", <id>, <relay>, <pubkey>]`
+                if uppercase {
+                    let mut tag = vec![String::from("E"), event_id.to_hex()];
+
+                    if relay_url.is_some() || public_key.is_some() {
+                        tag.push(relay_url.map(|u| u.to_string()).unwrap_or_default());
+                    }
+
+                    if let Some(public_key) = public_key {
+                        tag.push(public_key.to_hex());
+                    }
+
+                    return tag;
+                }
+
+                let mut tag = vec![String::from("e"), event_id.to_hex()];
+
+                // Optional values are positional: fill the gaps with empty strings
+                if relay_url.is_some() || marker.is_some() || public_key.is_some() {
+                    tag.push(relay_url.map(|u| u.to_string()).unwrap_or_default());
+                }
+
+                if marker.is_some() || public_key.is_some() {
+                    tag.push(marker.map(marker_to_str).unwrap_or_default().to_string());
+                }
+
+                if let Some(public_key) = public_key {
+                    tag.push(public_key.to_hex());
+                }
+
+                tag
+            }
+            Self::Quote {
+                event_id,
+                relay_url,
+                public_key,
+            } => {
+                let mut tag = vec![String::from("q"), event_id.to_hex()];
+
+                if relay_url.is_some() || public_key.is_some() {
+                    tag.push(relay_url.map(|u| u.to_string()).unwrap_or_default());
+                }
+
+                if let Some(public_key) = public_key {
+                    tag.push(public_key.to_hex());
+                }
+
+                tag
+            }
+            Self::PublicKeyTag {
+                public_key,
+                relay_url,
+                alias,
+                uppercase,
+            } => {
+                let kind: &str = if uppercase { "P" } else { "p" };
+                let mut tag = vec![kind.to_string(), public_key.to_hex()];
+
+                if relay_url.is_some() || alias.is_some() {
+                    tag.push(relay_url.map(|u| u.to_string()).unwrap_or_default());
+                }
+
+                if let Some(alias) = alias {
+                    tag.push(alias);
+                }
+
+                tag
+            }
+            Self::CoordinateTag {
+                coordinate,
+                relay_url,
+                uppercase,
+            } => {
+                let kind: &str = if uppercase { "A" } else { "a" };
+                let mut tag = vec![kind.to_string(), coordinate.to_string()];
+
+                if let Some(relay_url) = relay_url {
+                    tag.push(relay_url.to_string());
+                }
+
+                tag
+            }
+            Self::KindTag { kind, uppercase } => {
+                let tag_kind: &str = if uppercase { "K" } else { "k" };
+                vec![tag_kind.to_string(), kind.as_u16().to_string()]
+            }
+            Self::Identifier { identifier } => vec![String::from("d"), identifier],
+            Self::Hashtag { hashtag } => vec![String::from("t"), hashtag],
+            Self::Geohash { geohash } => vec![String::from("g"), geohash],
+            Self::Reference { reference } => vec![String::from("r"), reference],
+            Self::RelayMetadataTag {
+                relay_url,
+                metadata,
+            } => {
+                let mut tag = vec![String::from("r"), relay_url.to_string()];
+
+                if let Some(metadata) = metadata {
+                    tag.push(relay_metadata_to_str(metadata).to_string());
+                }
+
+                tag
+            }
+            Self::Relay { relay_url } => vec![String::from("relay"), relay_url.to_string()],
+            Self::Relays { relay_urls } => {
+                let mut tag = Vec::with_capacity(relay_urls.len() + 1);
+                tag.push(String::from("relays"));
+                tag.extend(relay_urls.into_iter().map(|u| u.to_string()));
+                tag
+            }
+            Self::Pow { nonce, difficulty } => {
+                vec![String::from("nonce"), nonce, difficulty.to_string()]
+            }
+            Self::Client {
+                name,
+                address,
+                relay_url,
+            } => {
+                let mut tag = vec![String::from("client"), name];
+
+                if let Some(address) = address {
+                    tag.push(address.to_string());
+
+                    if let Some(relay_url) = relay_url {
+                        tag.push(relay_url.to_string());
+                    }
+                }
+
+                tag
+            }
+            Self::ContentWarning { reason } => {
+                let mut tag = vec![String::from("content-warning")];
+
+                if let Some(reason) = reason {
+                    tag.push(reason);
+                }
+
+                tag
+            }
+            Self::Expiration { timestamp } => {
+                vec![String::from("expiration"), timestamp.as_secs().to_string()]
+            }
+            Self::Subject { subject } => vec![String::from("subject"), subject],
+            Self::Challenge { challenge } => vec![String::from("challenge"), challenge],
+            Self::Title { title } => vec![String::from("title"), title],
+            Self::Image { url, dimensions } => with_dimensions("image", url, dimensions),
+            Self::Thumb { url, dimensions } => with_dimensions("thumb", url, dimensions),
+            Self::Summary { summary } => vec![String::from("summary"), summary],
+            Self::PublishedAt { timestamp } => {
+                vec![
+                    String::from("published_at"),
+                    timestamp.as_secs().to_string(),
+                ]
+            }
+            Self::Description { desc } => vec![String::from("description"), desc],
+            Self::Bolt11 { bolt11 } => vec![String::from("bolt11"), bolt11],
+            Self::Preimage { preimage } => vec![String::from("preimage"), preimage],
+            Self::Amount { millisats, bolt11 } => {
+                let mut tag = vec![String::from("amount"), millisats.to_string()];
+
+                if let Some(bolt11) = bolt11 {
+                    tag.push(bolt11);
+                }
+
+                tag
+            }
+            Self::Lnurl { lnurl } => vec![String::from("lnurl"), lnurl],
+            Self::Zap {
+                public_key,
+                relay_url,
+                weight,
+            } => {
+                let mut tag = vec![String::from("zap"), public_key.to_hex()];
+
+                if relay_url.is_some() || weight.is_some() {
+                    tag.push(relay_url.map(|u| u.to_string()).unwrap_or_default());
+                }
+
+                if let Some(weight) = weight {
+                    tag.push(weight.to_string());
+                }
+
+                tag
+            }
+            Self::Anon { msg } => {
+                let mut tag = vec![String::from("anon")];
+
+                if let Some(msg) = msg {
+                    tag.push(msg);
+                }
+
+                tag
+            }
+            Self::Name { name } => vec![String::from("name"), name],
+            Self::Url { url } => vec![String::from("url"), url],
+            Self::MimeType { mime } => vec![String::from("m"), mime],
+            Self::Sha256 { hash } => vec![String::from("x"), hash],
+            Self::Size { size } => vec![String::from("size"), size.to_string()],
+            Self::Dim { dimensions } => {
+                vec![String::from("dim"), dimensions_to_string(&dimensions)]
+            }
+            Self::Magnet { uri } => vec![String::from("magnet"), uri],
+            Self::Blurhash { blurhash } => vec![String::from("blurhash"), blurhash],
+            Self::Emoji { shortcode, url } => vec![String::from("emoji"), shortcode, url],
+            Self::Word { word } => vec![String::from("word"), word],
+            Self::LabelNamespace { namespace } => vec![String::from("L"), namespace],
+            Self::Label { value, namespace } => {
+                let mut tag = vec![String::from("l"), value];
+
+                if let Some(namespace) = namespace {
+                    tag.push(namespace);
+                }
+
+                tag
+            }
+            Self::Imeta { metadata } => {
+                let mut tag = vec![String::from("imeta")];
+                tag.extend(metadata.to_imeta_values());
+                tag
+            }
+            Self::Alt { summary } => vec![String::from("alt"), summary],
+            Self::Encrypted => vec![String::from("encrypted")],
+            Self::Protected => vec![String::from("-")],
+        }
+    }
+}
+
+impl From<TagStandard> for event::Tag {
+    fn from(standard: TagStandard) -> Self {
+        let mut values: Vec<String> = standard.to_vec();
+        let kind: String = values.remove(0);
+        event::Tag::custom(kind.as_str(), values.as_slice())
+    }
+}
+
+fn non_empty(value: Option<&String>) -> Option<String> {
+    value.filter(|v| !v.is_empty()).cloned()
+}
+
+fn parse_event_id(value: Option<&String>) -> Option<Arc<EventId>> {
+    let id = nostr::EventId::from_hex(value?).ok()?;
+    Some(Arc::new(id.into()))
+}
+
+fn parse_public_key(value: Option<&String>) -> Option<Arc<PublicKey>> {
+    let public_key = nostr::PublicKey::from_hex(value?).ok()?;
+    Some(Arc::new(public_key.into()))
+}
+
+fn parse_relay_url(value: Option<&String>) -> Option<Arc<RelayUrl>> {
+    let url = nostr::RelayUrl::parse(value?).ok()?;
+    Some(Arc::new(url.into()))
+}
+
+fn parse_coordinate(value: Option<&String>) -> Option<Arc<Coordinate>> {
+    let coordinate = nip01::Coordinate::from_str(value?).ok()?;
+    Some(Arc::new(coordinate.into()))
+}
+
+fn parse_timestamp(value: Option<&String>) -> Option<Arc<Timestamp>> {
+    let secs: u64 = value?.parse().ok()?;
+    Some(Arc::new(nostr::Timestamp::from_secs(secs).into()))
+}
+
+fn parse_marker(value: &str) -> Option<Marker> {
+    match value {
+        "root" => Some(Marker::Root),
+        "reply" => Some(Marker::Reply),
+        _ => None,
+    }
+}
+
+fn marker_to_str(marker: Marker) -> &'static str {
+    match marker {
+        Marker::Root => "root",
+        Marker::Reply => "reply",
+    }
+}
+
+fn parse_relay_metadata(value: &str) -> Option<RelayMetadata> {
+    match value {
+        "read" => Some(RelayMetadata::Read),
+        "write" => Some(RelayMetadata::Write),
+        _ => None,
+    }
+}
+
+fn relay_metadata_to_str(metadata: RelayMetadata) -> &'static str {
+    match metadata {
+        RelayMetadata::Read => "read",
+        RelayMetadata::Write => "write",
+    }
+}
+
+/// Parse `<width>x<height>`
+fn parse_dimensions(value: &str) -> Option<ImageDimensions> {
+    let (width, height) = value.split_once('x')?;
+    Some(ImageDimensions {
+        width: width.parse().ok()?,
+        height: height.parse().ok()?,
+    })
+}
+
+fn dimensions_to_string(dimensions: &ImageDimensions) -> String {
+    format!("{}x{}", dimensions.width, dimensions.height)
+}
+
+fn with_dimensions(kind: &str, url: String, dimensions: Option<ImageDimensions>) -> Vec<String> {
+    let mut tag = vec![kind.to_string(), url];
+
+    if let Some(dimensions) = dimensions {
+        tag.push(dimensions_to_string(&dimensions));
+    }
+
+    tag
+}
@@ -3,7 +3,9 @@
 // Distributed under the MIT software license
 
 pub mod client;
+pub mod prefix;
 pub mod relay;
 
 pub use self::client::{ClientMessage, ClientMessageEnum};
+pub use self::prefix::MachineReadablePrefix;
 pub use self::relay::{RelayMessage, RelayMessageEnum};
@@ -0,0 +1,70 @@
+// Copyright (c) 2023-2025 Rust Nostr Developers
+// Distributed under the MIT software license
+
+use uniffi::Enum;
+
+/// Machine-readable prefix of the `OK` and `CLOSED` relay messages
+///
+/// <https://github.com/nostr-protocol/nips/blob/master/01.md>
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Enum)]
+pub enum MachineReadablePrefix {
+    /// `duplicate:`
+    Duplicate,
+    /// `pow:`
+    Pow,
+    /// `blocked:`
+    Blocked,
+    /// `rate-limited:`
+    RateLimited,
+    /// `invalid:`
+    Invalid,
+    /// `auth-required:`
+    AuthRequired,
+    /// `restricted:`
+    Restricted,
+    /// `error:`
+    Error,
+    /// `mute:`
+    Mute,
+}
+
+impl MachineReadablePrefix {
+    const ALL: [Self; 9] = [
+        Self::Duplicate,
+        Self::Pow,
+        Self::Blocked,
+        Self::RateLimited,
+        Self::Invalid,
+        Self::AuthRequired,
+        Self::Restricted,
+        Self::Error,
+        Self::Mute,
+    ];
+
+    fn as_str(&self) -> &'static str {
+        match self {
+            Self::Duplicate => "duplicate:",
+            Self::Pow => "pow:",
+            Self::Blocked => "blocked:",
+            Self::RateLimited => "rate-limited:",
+            Self::Invalid => "invalid:",
+            Self::AuthRequired => "auth-required:",
+            Self::Restricted => "restricted:",
+            Self::Error => "error:",
+            Self::Mute => "mute:",
+        }
+    }
+
+    /// Parse the prefix at the start of a relay message
+    pub fn parse(message: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|prefix| message.starts_with(prefix.as_str()))
+    }
+}
+
+/// Parse the machine-readable prefix of a relay message (i.e., `pow: difficulty 20 required`)
+#[uniffi::export]
+pub fn parse_machine_readable_prefix(message: &str) -> Option<MachineReadablePrefix> {
+    MachineReadablePrefix::parse(message)
+}
@@ -9,6 +9,7 @@ use std::sync::Arc;
 use nostr::SubscriptionId;
 use uniffi::{Enum, Object};
 
+use super::MachineReadablePrefix;
 use crate::error::Result;
 use crate::protocol::event::{Event, EventId};
 
@@ -253,4 +254,13 @@ impl RelayMessage {
     pub fn as_enum(&self) -> RelayMessageEnum {
         self.inner.clone().into()
     }
+
+    /// Get the machine-readable prefix of `OK` and `CLOSED` messages
+    pub fn machine_readable_prefix(&self) -> Option<MachineReadablePrefix> {
+        match &self.inner {
+            nostr::RelayMessage::Ok { message, .. }
+            | nostr::RelayMessage::Closed { message, .. } => MachineReadablePrefix::parse(message),
+            _ => None,
+        }
+    }
 }
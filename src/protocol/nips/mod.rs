@@ -3,6 +3,7 @@
 // Distributed under the MIT software license
 
 pub mod nip01;
+pub mod nip02;
 #[cfg(feature = "nip04")]
 pub mod nip04;
 pub mod nip05;
@@ -26,9 +27,11 @@ pub mod nip46;
 pub mod nip47;
 #[cfg(feature = "nip49")]
 pub mod nip49;
+pub mod nip51;
 pub mod nip56;
 pub mod nip57;
 #[cfg(feature = "nip59")]
 pub mod nip59;
 pub mod nip65;
 pub mod nip73;
+pub mod nip94;
@@ -0,0 +1,136 @@
+// Copyright (c) 2023-2025 Rust Nostr Developers
+// Distributed under the MIT software license
+
+//! Follow list
+//!
+//! <https://github.com/nostr-protocol/nips/blob/master/02.md>
+
+use std::ops::Deref;
+use std::sync::Arc;
+
+use uniffi::Object;
+
+use crate::error::{NostrSdkError, Result};
+use crate::protocol::event::{Event, EventBuilder};
+use crate::protocol::key::PublicKey;
+use crate::protocol::types::{Contact, RelayUrl};
+
+/// Contact list (kind `3`)
+///
+/// Keep the tags and the content (legacy relay list) of the latest contact list,
+/// so updating it doesn't drop anything.
+#[derive(Debug, Clone, PartialEq, Eq, Object)]
+pub struct ContactList {
+    tags: Vec<nostr::Tag>,
+    content: String,
+}
+
+impl ContactList {
+    fn contact_public_key(tag: &nostr::Tag) -> Option<&str> {
+        match tag.as_slice() {
+            [key, public_key, ..] if key == "p" => Some(public_key),
+            _ => None,
+        }
+    }
+
+    fn with_tags(&self, tags: Vec<nostr::Tag>) -> EventBuilder {
+        nostr::EventBuilder::new(nostr::Kind::ContactList, self.content.clone())
+            .tags(tags)
+            .into()
+    }
+}
+
+#[uniffi::export]
+impl ContactList {
+    /// Parse the contact list from an event
+    #[uniffi::constructor]
+    pub fn from_event(event: &Event) -> Result<Self> {
+        let event: &nostr::Event = event.deref();
+
+        if event.kind != nostr::Kind::ContactList {
+            return Err(NostrSdkError::Event {
+                msg: format!(
+                    "Expected kind {}, found {}",
+                    nostr::Kind::ContactList,
+                    event.kind
+                ),
+            });
+        }
+
+        Ok(Self {
+            tags: event.tags.iter().cloned().collect(),
+            content: event.content.clone(),
+        })
+    }
+
+    /// Get the contacts
+    pub fn contacts(&self) -> Vec<Contact> {
+        self.tags
+            .iter()
+            .filter_map(|tag| {
+                let public_key = nostr::PublicKey::from_hex(Self::contact_public_key(tag)?).ok()?;
+                let values: &[String] = tag.as_slice();
+
+                Some(Contact {
+                    public_key: Arc::new(public_key.into()),
+                    relay_url: values
+                        .get(2)
+                        .and_then(|url| nostr::RelayUrl::parse(url).ok())
+                        .map(|url| Arc::new(RelayUrl::from(url))),
+                    alias: values.get(3).filter(|alias| !alias.is_empty()).cloned(),
+                })
+            })
+            .collect()
+    }
+
+    /// Check if the public key is in the contact list
+    pub fn is_following(&self, public_key: &PublicKey) -> bool {
+        let public_key: String = public_key.to_hex();
+        self.tags
+            .iter()
+            .any(|tag| Self::contact_public_key(tag) == Some(public_key.as_str()))
+    }
+
+    /// Get the content (legacy relay list, if any)
+    pub fn content(&self) -> String {
+        self.content.clone()
+    }
+
+    /// Build the updated contact list with the new contact
+    ///
+    /// The existing tags and content are preserved. If the contact is already followed, the list is left unchanged.
+    pub fn follow(&self, contact: Contact) -> Result<EventBuilder> {
+        let mut tags: Vec<nostr::Tag> = self.tags.clone();
+
+        if !self.is_following(&contact.public_key) {
+            let mut values: Vec<String> = vec![String::from("p"), contact.public_key.to_hex()];
+
+            match (contact.relay_url, contact.alias) {
+                (relay_url, Some(alias)) => {
+                    values.push(relay_url.map(|u| u.to_string()).unwrap_or_default());
+                    values.push(alias);
+                }
+                (Some(relay_url), None) => values.push(relay_url.to_string()),
+                (None, None) => {}
+            }
+
+            tags.push(nostr::Tag::parse(values)?);
+        }
+
+        Ok(self.with_tags(tags))
+    }
+
+    /// Build the updated contact list without the public key
+    ///
+    /// The other tags and the content are preserved.
+    pub fn unfollow(&self, public_key: &PublicKey) -> EventBuilder {
+        let public_key: String = public_key.to_hex();
+        let tags: Vec<nostr::Tag> = self
+            .tags
+            .iter()
+            .filter(|tag| Self::contact_public_key(tag) != Some(public_key.as_str()))
+            .cloned()
+            .collect();
+        self.with_tags(tags)
+    }
+}
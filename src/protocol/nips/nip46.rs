@@ -14,6 +14,7 @@ use uniffi::{Enum, Object};
 use crate::error::{NostrSdkError, Result};
 use crate::protocol::event::UnsignedEvent;
 use crate::protocol::key::PublicKey;
+use crate::protocol::types::RelayUrl;
 
 /// Nostr Connect Request (NIP46)
 #[derive(Enum)]
@@ -102,6 +103,95 @@ impl From<nip46::NostrConnectRequest> for NostrConnectRequest {
     }
 }
 
+impl NostrConnectRequest {
+    pub(crate) fn method(&self) -> NostrConnectMethod {
+        match self {
+            Self::Connect { .. } => NostrConnectMethod::Connect,
+            Self::GetPublicKey => NostrConnectMethod::GetPublicKey,
+            Self::SignEvent { .. } => NostrConnectMethod::SignEvent,
+            Self::Nip04Encrypt { .. } => NostrConnectMethod::Nip04Encrypt,
+            Self::Nip04Decrypt { .. } => NostrConnectMethod::Nip04Decrypt,
+            Self::Nip44Encrypt { .. } => NostrConnectMethod::Nip44Encrypt,
+            Self::Nip44Decrypt { .. } => NostrConnectMethod::Nip44Decrypt,
+            Self::Ping => NostrConnectMethod::Ping,
+        }
+    }
+}
+
+/// Nostr Connect method
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Enum)]
+pub enum NostrConnectMethod {
+    Connect,
+    GetPublicKey,
+    SignEvent,
+    Nip04Encrypt,
+    Nip04Decrypt,
+    Nip44Encrypt,
+    Nip44Decrypt,
+    Ping,
+}
+
+impl fmt::Display for NostrConnectMethod {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Connect => f.write_str("connect"),
+            Self::GetPublicKey => f.write_str("get_public_key"),
+            Self::SignEvent => f.write_str("sign_event"),
+            Self::Nip04Encrypt => f.write_str("nip04_encrypt"),
+            Self::Nip04Decrypt => f.write_str("nip04_decrypt"),
+            Self::Nip44Encrypt => f.write_str("nip44_encrypt"),
+            Self::Nip44Decrypt => f.write_str("nip44_decrypt"),
+            Self::Ping => f.write_str("ping"),
+        }
+    }
+}
+
+/// Nostr Connect permission
+///
+/// <https://github.com/nostr-protocol/nips/blob/master/46.md#requested-permissions>
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Enum)]
+pub enum NostrConnectPermission {
+    /// Allow a method
+    Method { method: NostrConnectMethod },
+    /// Allow signing events of a specific kind
+    SignEvent { kind: u16 },
+}
+
+impl fmt::Display for NostrConnectPermission {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Method { method } => write!(f, "{method}"),
+            Self::SignEvent { kind } => write!(f, "{}:{kind}", NostrConnectMethod::SignEvent),
+        }
+    }
+}
+
+impl NostrConnectPermission {
+    /// Check if the permissions allow the request
+    ///
+    /// `connect` and `ping` requests are always allowed.
+    pub(crate) fn allows(permissions: &[Self], req: &NostrConnectRequest) -> bool {
+        let method: NostrConnectMethod = req.method();
+
+        if matches!(
+            method,
+            NostrConnectMethod::Connect | NostrConnectMethod::Ping
+        ) {
+            return true;
+        }
+
+        permissions
+            .iter()
+            .any(|permission| match (permission, req) {
+                (Self::Method { method: allowed }, _) => *allowed == method,
+                (Self::SignEvent { kind }, NostrConnectRequest::SignEvent { unsigned_event }) => {
+                    unsigned_event.kind.as_u16() == *kind
+                }
+                (Self::SignEvent { .. }, _) => false,
+            })
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Object)]
 #[uniffi::export(Debug, Eq, Hash)]
 pub struct NostrConnectMetadata {
@@ -194,6 +284,99 @@ impl NostrConnectUri {
     }
 }
 
+/// Client URI builder (`nostrconnect://`)
+///
+/// Use `as_string` to get the URI to show as QR code,
+/// since it includes the requested permissions and the secret.
+///
+/// <https://github.com/nostr-protocol/nips/blob/master/46.md>
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Object)]
+#[uniffi::export(Debug, Eq, Hash)]
+pub struct NostrConnectUriBuilder {
+    public_key: nostr::PublicKey,
+    relays: Vec<nostr::RelayUrl>,
+    metadata: nip46::NostrConnectMetadata,
+    secret: Option<String>,
+    perms: Vec<NostrConnectPermission>,
+}
+
+#[uniffi::export]
+impl NostrConnectUriBuilder {
+    #[uniffi::constructor]
+    pub fn new(
+        public_key: &PublicKey,
+        relays: Vec<Arc<RelayUrl>>,
+        metadata: &NostrConnectMetadata,
+    ) -> Self {
+        Self {
+            public_key: **public_key,
+            relays: relays
+                .into_iter()
+                .map(|u| u.as_ref().deref().clone())
+                .collect(),
+            metadata: metadata.deref().clone(),
+            secret: None,
+            perms: Vec::new(),
+        }
+    }
+
+    /// Secret that the remote signer must return in the `connect` response
+    pub fn secret(&self, secret: &str) -> Self {
+        let mut builder = self.clone();
+        builder.secret = Some(secret.to_string());
+        builder
+    }
+
+    /// Requested permissions
+    pub fn perms(&self, perms: Vec<NostrConnectPermission>) -> Self {
+        let mut builder = self.clone();
+        builder.perms = perms;
+        builder
+    }
+
+    /// Get the URI as string, including the requested permissions and the secret
+    pub fn as_string(&self) -> Result<String> {
+        let mut url: Url = Url::parse(&format!("nostrconnect://{}", self.public_key.to_hex()))?;
+
+        {
+            let mut query = url.query_pairs_mut();
+
+            for relay in self.relays.iter() {
+                query.append_pair("relay", &relay.to_string());
+            }
+
+            if let Some(secret) = &self.secret {
+                query.append_pair("secret", secret);
+            }
+
+            if !self.perms.is_empty() {
+                let perms: Vec<String> = self.perms.iter().map(|p| p.to_string()).collect();
+                query.append_pair("perms", &perms.join(","));
+            }
+
+            query.append_pair("name", &self.metadata.name);
+
+            if let Some(app_url) = &self.metadata.url {
+                query.append_pair("url", app_url.as_str());
+            }
+
+            if let Some(icon) = self.metadata.icons.as_ref().and_then(|i| i.first()) {
+                query.append_pair("image", icon.as_str());
+            }
+
+            let metadata: String = self.metadata.try_as_json()?;
+            query.append_pair("metadata", &metadata);
+        }
+
+        Ok(url.to_string())
+    }
+
+    /// Build URI
+    pub fn build(&self) -> Result<NostrConnectUri> {
+        NostrConnectUri::parse(&self.as_string()?)
+    }
+}
+
 #[derive(Enum)]
 pub enum NostrConnectMessage {
     Request {
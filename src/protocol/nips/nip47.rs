@@ -7,15 +7,16 @@ use std::str::FromStr;
 use std::sync::Arc;
 
 use nostr::nips::nip47;
+use nostr::serde_json::{self, Value};
 use uniffi::{Enum, Object, Record};
 
-use crate::error::Result;
+use crate::error::{NostrSdkError, Result};
 use crate::protocol::key::{PublicKey, SecretKey};
 use crate::protocol::types::{RelayUrl, Timestamp};
 use crate::protocol::util::JsonValue;
 
 /// NIP47 Response Error codes
-#[derive(Enum)]
+#[derive(Debug, Enum)]
 pub enum ErrorCode {
     ///  The client is sending commands too fast.
     RateLimited,
@@ -1118,6 +1119,121 @@ impl Response {
     }
 }
 
+/// Result of a `multi_pay_invoice` item
+#[derive(Enum)]
+pub enum MultiPayInvoiceResult {
+    /// Invoice paid
+    Paid { response: PayInvoiceResponse },
+    /// Payment failed
+    Failed { error: NIP47Error },
+}
+
+/// Result of a `multi_pay_keysend` item
+#[derive(Enum)]
+pub enum MultiPayKeysendResult {
+    /// Keysend paid
+    Paid { response: PayKeysendResponse },
+    /// Payment failed
+    Failed { error: NIP47Error },
+}
+
+/// Hold invoice accepted notification
+#[derive(Record)]
+pub struct HoldInvoiceAcceptedNotification {
+    /// Bolt11 invoice
+    pub invoice: String,
+    /// Invoice's description
+    pub description: Option<String>,
+    /// Invoice's description hash
+    pub description_hash: Option<String>,
+    /// Payment hash
+    pub payment_hash: String,
+    /// Amount in millisatoshis
+    pub amount: u64,
+    /// Creation timestamp in seconds since epoch
+    pub created_at: Arc<Timestamp>,
+    /// Expiration timestamp in seconds since epoch
+    pub expires_at: Option<Arc<Timestamp>>,
+    /// Block height by which the invoice must be settled or canceled
+    pub settle_deadline: Option<u64>,
+    /// Optional metadata about the payment
+    pub metadata: Option<JsonValue>,
+}
+
+impl HoldInvoiceAcceptedNotification {
+    fn from_value(value: Value) -> Result<Self> {
+        let string = |key: &str| value.get(key).and_then(Value::as_str).map(String::from);
+        let number = |key: &str| value.get(key).and_then(Value::as_u64);
+        let missing = |key: &str| NostrSdkError::Nip47 {
+            msg: format!("Missing '{key}' in hold invoice notification"),
+        };
+
+        Ok(Self {
+            invoice: string("invoice").ok_or_else(|| missing("invoice"))?,
+            description: string("description"),
+            description_hash: string("description_hash"),
+            payment_hash: string("payment_hash").ok_or_else(|| missing("payment_hash"))?,
+            amount: number("amount").ok_or_else(|| missing("amount"))?,
+            created_at: Arc::new(
+                nostr::Timestamp::from_secs(
+                    number("created_at").ok_or_else(|| missing("created_at"))?,
+                )
+                .into(),
+            ),
+            expires_at: number("expires_at")
+                .map(|t| Arc::new(nostr::Timestamp::from_secs(t).into())),
+            settle_deadline: number("settle_deadline"),
+            metadata: value
+                .get("metadata")
+                .cloned()
+                .and_then(|m| m.try_into().ok()),
+        })
+    }
+}
+
+/// Wallet notification
+///
+/// <https://github.com/nostr-protocol/nips/blob/master/47.md#notifications>
+#[derive(Enum)]
+pub enum WalletNotification {
+    /// Payment received
+    PaymentReceived { payment: LookupInvoiceResponse },
+    /// Payment sent
+    PaymentSent { payment: LookupInvoiceResponse },
+    /// Hold invoice accepted, waiting to be settled or canceled
+    HoldInvoiceAccepted {
+        notification: HoldInvoiceAcceptedNotification,
+    },
+}
+
+impl WalletNotification {
+    /// Parse the decrypted content of a notification event (kind `23196` or `23197`)
+    ///
+    /// Return `None` if the notification type is unknown.
+    pub(crate) fn from_json(json: &str) -> Result<Option<Self>> {
+        let mut value: Value = serde_json::from_str(json)?;
+        let notification: Value = value
+            .get_mut("notification")
+            .map(Value::take)
+            .unwrap_or_default();
+
+        match value.get("notification_type").and_then(Value::as_str) {
+            Some("payment_received") => Ok(Some(Self::PaymentReceived {
+                payment: serde_json::from_value::<nip47::LookupInvoiceResponse>(notification)?
+                    .into(),
+            })),
+            Some("payment_sent") => Ok(Some(Self::PaymentSent {
+                payment: serde_json::from_value::<nip47::LookupInvoiceResponse>(notification)?
+                    .into(),
+            })),
+            Some("hold_invoice_accepted") => Ok(Some(Self::HoldInvoiceAccepted {
+                notification: HoldInvoiceAcceptedNotification::from_value(notification)?,
+            })),
+            _ => Ok(None),
+        }
+    }
+}
+
 /// Nostr Connect URI
 #[derive(Debug, PartialEq, Eq, Object)]
 #[uniffi::export(Debug, Display, Eq)]
@@ -0,0 +1,655 @@
+// Copyright (c) 2023-2025 Rust Nostr Developers
+// Distributed under the MIT software license
+
+//! Lists
+//!
+//! Public entries are stored as tags, private ones as NIP-44 encrypted JSON array of tags in the content.
+//!
+//! <https://github.com/nostr-protocol/nips/blob/master/51.md>
+
+use std::ops::Deref;
+use std::str::FromStr;
+use std::sync::Arc;
+
+use nostr::nips::nip01;
+use nostr::serde_json;
+use uniffi::{Object, Record};
+
+use crate::error::{NostrSdkError, Result};
+use crate::protocol::event::{Event, EventBuilder, EventId};
+use crate::protocol::key::PublicKey;
+use crate::protocol::nips::nip01::Coordinate;
+use crate::protocol::signer::AsyncNostrSigner;
+use crate::protocol::types::RelayUrl;
+
+/// List entries, as tag values
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+struct ListEntries {
+    identifier: Option<String>,
+    public: Vec<Vec<String>>,
+    private: Vec<Vec<String>>,
+    /// The event has private entries that haven't been decrypted
+    locked: bool,
+}
+
+impl ListEntries {
+    fn with_identifier(identifier: String) -> Self {
+        Self {
+            identifier: Some(identifier),
+            ..Default::default()
+        }
+    }
+
+    /// Parse the event tags and decrypt the private entries
+    ///
+    /// The private entries are decrypted only if the signer is the author of the event.
+    async fn parse(
+        kind: nostr::Kind,
+        event: &nostr::Event,
+        signer: Option<&Arc<dyn AsyncNostrSigner>>,
+    ) -> Result<Self> {
+        if event.kind != kind {
+            return Err(NostrSdkError::Event {
+                msg: format!("Expected kind {kind}, found {}", event.kind),
+            });
+        }
+
+        let mut entries: Self = Self::default();
+
+        for tag in event.tags.iter() {
+            let values: &[String] = tag.as_slice();
+
+            match values.first().map(String::as_str) {
+                Some("d") => entries.identifier = values.get(1).cloned(),
+                Some(..) => entries.public.push(values.to_vec()),
+                None => {}
+            }
+        }
+
+        if event.content.is_empty() {
+            return Ok(entries);
+        }
+
+        let signer: &Arc<dyn AsyncNostrSigner> = match signer {
+            Some(signer) => signer,
+            None => {
+                entries.locked = true;
+                return Ok(entries);
+            }
+        };
+        let public_key: Arc<PublicKey> = public_key(signer).await?;
+
+        // Private entries of another user: only the author can decrypt them
+        if **public_key != event.pubkey {
+            entries.locked = true;
+            return Ok(entries);
+        }
+
+        // Legacy NIP-04 encrypted content
+        let json: String = if event.content.contains("?iv=") {
+            signer
+                .nip04_decrypt_async(public_key, event.content.clone())
+                .await?
+        } else {
+            signer
+                .nip44_decrypt_async(public_key, event.content.clone())
+                .await?
+        };
+
+        entries.private = serde_json::from_str(&json)?;
+
+        Ok(entries)
+    }
+
+    /// Build the event, encrypting the private entries
+    async fn build(
+        &self,
+        kind: nostr::Kind,
+        signer: &Arc<dyn AsyncNostrSigner>,
+    ) -> Result<EventBuilder> {
+        // Don't drop the private entries that couldn't be decrypted
+        if self.locked {
+            return Err(NostrSdkError::Signer {
+                msg: String::from(
+                    "Private entries not decrypted: parse the list with the signer of the author",
+                ),
+            });
+        }
+
+        let mut tags: Vec<nostr::Tag> = Vec::with_capacity(self.public.len() + 1);
+
+        if let Some(identifier) = &self.identifier {
+            tags.push(nostr::Tag::identifier(identifier));
+        }
+
+        for values in self.public.iter() {
+            tags.push(nostr::Tag::parse(values.clone())?);
+        }
+
+        let content: String = if self.private.is_empty() {
+            String::new()
+        } else {
+            let json: String = serde_json::to_string(&self.private)?;
+            signer
+                .nip44_encrypt_async(public_key(signer).await?, json)
+                .await?
+        };
+
+        Ok(nostr::EventBuilder::new(kind, content).tags(tags).into())
+    }
+
+    /// Get the values of the entries with the tag key
+    fn values(&self, key: &str, private: bool) -> impl Iterator<Item = &[String]> {
+        let entries: &Vec<Vec<String>> = if private { &self.private } else { &self.public };
+
+        entries
+            .iter()
+            .filter(move |values| values.first().map(String::as_str) == Some(key))
+            .map(|values| &values[1..])
+    }
+
+    fn strings(&self, key: &str, private: bool) -> Vec<String> {
+        self.values(key, private)
+            .filter_map(|values| values.first().cloned())
+            .collect()
+    }
+
+    /// Add an entry, moving it if already in the other section
+    fn add(&self, values: Vec<String>, private: bool) -> Self {
+        let mut entries: Self = self.remove(&values[0], &values[1]);
+
+        if private {
+            entries.private.push(values);
+        } else {
+            entries.public.push(values);
+        }
+
+        entries
+    }
+
+    /// Remove the entries with the tag key and value, from both sections
+    fn remove(&self, key: &str, value: &str) -> Self {
+        let mut entries: Self = self.clone();
+        let matches = |values: &Vec<String>| {
+            values.first().map(String::as_str) == Some(key)
+                && values.get(1).map(String::as_str) == Some(value)
+        };
+        entries.public.retain(|values| !matches(values));
+        entries.private.retain(|values| !matches(values));
+        entries
+    }
+
+    fn public_keys(&self, private: bool) -> Vec<Arc<PublicKey>> {
+        self.strings("p", private)
+            .into_iter()
+            .filter_map(|pk| nostr::PublicKey::from_hex(&pk).ok())
+            .map(|pk| Arc::new(pk.into()))
+            .collect()
+    }
+
+    fn event_ids(&self, private: bool) -> Vec<Arc<EventId>> {
+        self.strings("e", private)
+            .into_iter()
+            .filter_map(|id| nostr::EventId::from_hex(&id).ok())
+            .map(|id| Arc::new(id.into()))
+            .collect()
+    }
+
+    fn coordinates(&self, private: bool) -> Vec<Arc<Coordinate>> {
+        self.strings("a", private)
+            .into_iter()
+            .filter_map(|c| nip01::Coordinate::from_str(&c).ok())
+            .map(|c| Arc::new(c.into()))
+            .collect()
+    }
+}
+
+async fn public_key(signer: &Arc<dyn AsyncNostrSigner>) -> Result<Arc<PublicKey>> {
+    signer
+        .get_public_key_async()
+        .await?
+        .ok_or_else(|| NostrSdkError::Signer {
+            msg: String::from("Received None instead of public key"),
+        })
+}
+
+fn tag(key: &str, value: String) -> Vec<String> {
+    vec![key.to_string(), value]
+}
+
+macro_rules! impl_list {
+    ($name:ident, $kind:expr) => {
+        #[uniffi::export(async_runtime = "tokio")]
+        impl $name {
+            /// Parse the list from an event, decrypting the private entries with the signer
+            ///
+            /// The private entries are decrypted only if the signer is the author of the event:
+            /// otherwise (i.e., the list of another user) they are left empty.
+            #[uniffi::constructor(default(signer = None))]
+            pub async fn from_event(
+                event: &Event,
+                signer: Option<Arc<dyn AsyncNostrSigner>>,
+            ) -> Result<Self> {
+                Ok(Self {
+                    entries: ListEntries::parse($kind, event.deref(), signer.as_ref()).await?,
+                })
+            }
+
+            /// Build the list event, encrypting the private entries (NIP-44) with the signer
+            ///
+            /// Fails if the list was parsed without decrypting its private entries.
+            pub async fn to_event_builder(
+                &self,
+                signer: Arc<dyn AsyncNostrSigner>,
+            ) -> Result<EventBuilder> {
+                self.entries.build($kind, &signer).await
+            }
+        }
+    };
+}
+
+/// Mute list (kind `10000`)
+#[derive(Debug, Clone, PartialEq, Eq, Object)]
+pub struct MuteList {
+    entries: ListEntries,
+}
+
+impl_list!(MuteList, nostr::Kind::MuteList);
+
+#[uniffi::export]
+impl MuteList {
+    /// New empty mute list
+    #[uniffi::constructor]
+    pub fn new() -> Self {
+        Self {
+            entries: ListEntries::default(),
+        }
+    }
+
+    /// Muted public keys
+    #[uniffi::method(default(private = false))]
+    pub fn public_keys(&self, private: bool) -> Vec<Arc<PublicKey>> {
+        self.entries.public_keys(private)
+    }
+
+    /// Muted hashtags
+    #[uniffi::method(default(private = false))]
+    pub fn hashtags(&self, private: bool) -> Vec<String> {
+        self.entries.strings("t", private)
+    }
+
+    /// Muted threads
+    #[uniffi::method(default(private = false))]
+    pub fn event_ids(&self, private: bool) -> Vec<Arc<EventId>> {
+        self.entries.event_ids(private)
+    }
+
+    /// Muted words
+    #[uniffi::method(default(private = false))]
+    pub fn words(&self, private: bool) -> Vec<String> {
+        self.entries.strings("word", private)
+    }
+
+    #[uniffi::method(default(private = false))]
+    pub fn add_public_key(&self, public_key: &PublicKey, private: bool) -> Self {
+        Self {
+            entries: self.entries.add(tag("p", public_key.to_hex()), private),
+        }
+    }
+
+    pub fn remove_public_key(&self, public_key: &PublicKey) -> Self {
+        Self {
+            entries: self.entries.remove("p", &public_key.to_hex()),
+        }
+    }
+
+    #[uniffi::method(default(private = false))]
+    pub fn add_hashtag(&self, hashtag: &str, private: bool) -> Self {
+        Self {
+            entries: self.entries.add(tag("t", hashtag.to_lowercase()), private),
+        }
+    }
+
+    pub fn remove_hashtag(&self, hashtag: &str) -> Self {
+        Self {
+            entries: self.entries.remove("t", &hashtag.to_lowercase()),
+        }
+    }
+
+    #[uniffi::method(default(private = false))]
+    pub fn add_event_id(&self, event_id: &EventId, private: bool) -> Self {
+        Self {
+            entries: self.entries.add(tag("e", event_id.to_hex()), private),
+        }
+    }
+
+    pub fn remove_event_id(&self, event_id: &EventId) -> Self {
+        Self {
+            entries: self.entries.remove("e", &event_id.to_hex()),
+        }
+    }
+
+    #[uniffi::method(default(private = false))]
+    pub fn add_word(&self, word: &str, private: bool) -> Self {
+        Self {
+            entries: self.entries.add(tag("word", word.to_lowercase()), private),
+        }
+    }
+
+    pub fn remove_word(&self, word: &str) -> Self {
+        Self {
+            entries: self.entries.remove("word", &word.to_lowercase()),
+        }
+    }
+}
+
+/// Bookmarks (kind `10003`)
+#[derive(Debug, Clone, PartialEq, Eq, Object)]
+pub struct Bookmarks {
+    entries: ListEntries,
+}
+
+impl_list!(Bookmarks, nostr::Kind::Bookmarks);
+
+#[uniffi::export]
+impl Bookmarks {
+    /// New empty bookmarks list
+    #[uniffi::constructor]
+    pub fn new() -> Self {
+        Self {
+            entries: ListEntries::default(),
+        }
+    }
+
+    /// Bookmarked notes
+    #[uniffi::method(default(private = false))]
+    pub fn event_ids(&self, private: bool) -> Vec<Arc<EventId>> {
+        self.entries.event_ids(private)
+    }
+
+    /// Bookmarked articles
+    #[uniffi::method(default(private = false))]
+    pub fn coordinates(&self, private: bool) -> Vec<Arc<Coordinate>> {
+        self.entries.coordinates(private)
+    }
+
+    /// Bookmarked hashtags
+    #[uniffi::method(default(private = false))]
+    pub fn hashtags(&self, private: bool) -> Vec<String> {
+        self.entries.strings("t", private)
+    }
+
+    /// Bookmarked URLs
+    #[uniffi::method(default(private = false))]
+    pub fn urls(&self, private: bool) -> Vec<String> {
+        self.entries.strings("r", private)
+    }
+
+    #[uniffi::method(default(private = false))]
+    pub fn add_event_id(&self, event_id: &EventId, private: bool) -> Self {
+        Self {
+            entries: self.entries.add(tag("e", event_id.to_hex()), private),
+        }
+    }
+
+    pub fn remove_event_id(&self, event_id: &EventId) -> Self {
+        Self {
+            entries: self.entries.remove("e", &event_id.to_hex()),
+        }
+    }
+
+    #[uniffi::method(default(private = false))]
+    pub fn add_coordinate(&self, coordinate: &Coordinate, private: bool) -> Self {
+        Self {
+            entries: self.entries.add(tag("a", coordinate.to_string()), private),
+        }
+    }
+
+    pub fn remove_coordinate(&self, coordinate: &Coordinate) -> Self {
+        Self {
+            entries: self.entries.remove("a", &coordinate.to_string()),
+        }
+    }
+
+    #[uniffi::method(default(private = false))]
+    pub fn add_hashtag(&self, hashtag: &str, private: bool) -> Self {
+        Self {
+            entries: self.entries.add(tag("t", hashtag.to_lowercase()), private),
+        }
+    }
+
+    pub fn remove_hashtag(&self, hashtag: &str) -> Self {
+        Self {
+            entries: self.entries.remove("t", &hashtag.to_lowercase()),
+        }
+    }
+
+    #[uniffi::method(default(private = false))]
+    pub fn add_url(&self, url: &str, private: bool) -> Self {
+        Self {
+            entries: self.entries.add(tag("r", url.to_string()), private),
+        }
+    }
+
+    pub fn remove_url(&self, url: &str) -> Self {
+        Self {
+            entries: self.entries.remove("r", url),
+        }
+    }
+}
+
+/// Interests (kind `10015`)
+#[derive(Debug, Clone, PartialEq, Eq, Object)]
+pub struct Interests {
+    entries: ListEntries,
+}
+
+impl_list!(Interests, nostr::Kind::Interests);
+
+#[uniffi::export]
+impl Interests {
+    /// New empty interests list
+    #[uniffi::constructor]
+    pub fn new() -> Self {
+        Self {
+            entries: ListEntries::default(),
+        }
+    }
+
+    /// Hashtags
+    #[uniffi::method(default(private = false))]
+    pub fn hashtags(&self, private: bool) -> Vec<String> {
+        self.entries.strings("t", private)
+    }
+
+    /// Interest sets
+    #[uniffi::method(default(private = false))]
+    pub fn coordinates(&self, private: bool) -> Vec<Arc<Coordinate>> {
+        self.entries.coordinates(private)
+    }
+
+    #[uniffi::method(default(private = false))]
+    pub fn add_hashtag(&self, hashtag: &str, private: bool) -> Self {
+        Self {
+            entries: self.entries.add(tag("t", hashtag.to_lowercase()), private),
+        }
+    }
+
+    pub fn remove_hashtag(&self, hashtag: &str) -> Self {
+        Self {
+            entries: self.entries.remove("t", &hashtag.to_lowercase()),
+        }
+    }
+
+    #[uniffi::method(default(private = false))]
+    pub fn add_coordinate(&self, coordinate: &Coordinate, private: bool) -> Self {
+        Self {
+            entries: self.entries.add(tag("a", coordinate.to_string()), private),
+        }
+    }
+
+    pub fn remove_coordinate(&self, coordinate: &Coordinate) -> Self {
+        Self {
+            entries: self.entries.remove("a", &coordinate.to_string()),
+        }
+    }
+}
+
+/// Follow set (kind `30000`)
+#[derive(Debug, Clone, PartialEq, Eq, Object)]
+pub struct FollowSet {
+    entries: ListEntries,
+}
+
+impl_list!(FollowSet, nostr::Kind::FollowSet);
+
+#[uniffi::export]
+impl FollowSet {
+    /// New empty follow set
+    #[uniffi::constructor]
+    pub fn new(identifier: String) -> Self {
+        Self {
+            entries: ListEntries::with_identifier(identifier),
+        }
+    }
+
+    /// Set identifier (`d` tag)
+    pub fn identifier(&self) -> Option<String> {
+        self.entries.identifier.clone()
+    }
+
+    /// Public keys
+    #[uniffi::method(default(private = false))]
+    pub fn public_keys(&self, private: bool) -> Vec<Arc<PublicKey>> {
+        self.entries.public_keys(private)
+    }
+
+    #[uniffi::method(default(private = false))]
+    pub fn add_public_key(&self, public_key: &PublicKey, private: bool) -> Self {
+        Self {
+            entries: self.entries.add(tag("p", public_key.to_hex()), private),
+        }
+    }
+
+    pub fn remove_public_key(&self, public_key: &PublicKey) -> Self {
+        Self {
+            entries: self.entries.remove("p", &public_key.to_hex()),
+        }
+    }
+}
+
+/// Relay set (kind `30002`)
+#[derive(Debug, Clone, PartialEq, Eq, Object)]
+pub struct RelaySet {
+    entries: ListEntries,
+}
+
+impl_list!(RelaySet, nostr::Kind::RelaySet);
+
+#[uniffi::export]
+impl RelaySet {
+    /// New empty relay set
+    #[uniffi::constructor]
+    pub fn new(identifier: String) -> Self {
+        Self {
+            entries: ListEntries::with_identifier(identifier),
+        }
+    }
+
+    /// Set identifier (`d` tag)
+    pub fn identifier(&self) -> Option<String> {
+        self.entries.identifier.clone()
+    }
+
+    /// Relays
+    #[uniffi::method(default(private = false))]
+    pub fn relays(&self, private: bool) -> Vec<Arc<RelayUrl>> {
+        self.entries
+            .strings("relay", private)
+            .into_iter()
+            .filter_map(|url| nostr::RelayUrl::parse(&url).ok())
+            .map(|url| Arc::new(url.into()))
+            .collect()
+    }
+
+    #[uniffi::method(default(private = false))]
+    pub fn add_relay(&self, relay_url: &RelayUrl, private: bool) -> Self {
+        Self {
+            entries: self
+                .entries
+                .add(tag("relay", relay_url.to_string()), private),
+        }
+    }
+
+    pub fn remove_relay(&self, relay_url: &RelayUrl) -> Self {
+        Self {
+            entries: self.entries.remove("relay", &relay_url.to_string()),
+        }
+    }
+}
+
+/// Emoji
+///
+/// <https://github.com/nostr-protocol/nips/blob/master/30.md>
+#[derive(Record)]
+pub struct Emoji {
+    /// Shortcode (without colons)
+    pub shortcode: String,
+    /// Image URL
+    pub url: String,
+}
+
+/// Emoji set (kind `30030`)
+#[derive(Debug, Clone, PartialEq, Eq, Object)]
+pub struct EmojiSet {
+    entries: ListEntries,
+}
+
+impl_list!(EmojiSet, nostr::Kind::EmojiSet);
+
+#[uniffi::export]
+impl EmojiSet {
+    /// New empty emoji set
+    #[uniffi::constructor]
+    pub fn new(identifier: String) -> Self {
+        Self {
+            entries: ListEntries::with_identifier(identifier),
+        }
+    }
+
+    /// Set identifier (`d` tag)
+    pub fn identifier(&self) -> Option<String> {
+        self.entries.identifier.clone()
+    }
+
+    /// Emojis
+    #[uniffi::method(default(private = false))]
+    pub fn emojis(&self, private: bool) -> Vec<Emoji> {
+        self.entries
+            .values("emoji", private)
+            .filter_map(|values| match values {
+                [shortcode, url, ..] => Some(Emoji {
+                    shortcode: shortcode.clone(),
+                    url: url.clone(),
+                }),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[uniffi::method(default(private = false))]
+    pub fn add_emoji(&self, emoji: Emoji, private: bool) -> Self {
+        Self {
+            entries: self.entries.add(
+                vec![String::from("emoji"), emoji.shortcode, emoji.url],
+                private,
+            ),
+        }
+    }
+
+    pub fn remove_emoji(&self, shortcode: &str) -> Self {
+        Self {
+            entries: self.entries.remove("emoji", shortcode),
+        }
+    }
+}
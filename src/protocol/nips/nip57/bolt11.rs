@@ -0,0 +1,279 @@
+// Copyright (c) 2023-2025 Rust Nostr Developers
+// Distributed under the MIT software license
+
+//! Minimal BOLT11 decoder
+//!
+//! Extract only the fields needed to validate zap receipts and payments.
+//!
+//! <https://github.com/lightning/bolts/blob/master/11-payment-encoding.md>
+
+const CHARSET: &str = "qpzry9x8gf2tvdw0s3jn54khce6mua7l";
+const GENERATOR: [u32; 5] = [0x3b6a57b2, 0x26508e6d, 0x1ea119fa, 0x3d4233dd, 0x2a1462b3];
+
+/// Timestamp len (5-bit groups)
+const TIMESTAMP_LEN: usize = 7;
+/// Signature + recovery ID len (5-bit groups)
+const SIGNATURE_LEN: usize = 104;
+/// Checksum len (5-bit groups)
+const CHECKSUM_LEN: usize = 6;
+
+/// `p` tagged field
+const PAYMENT_HASH_FIELD: u8 = 1;
+/// `h` tagged field
+const DESCRIPTION_HASH_FIELD: u8 = 23;
+
+/// Decoded BOLT11 invoice
+pub(crate) struct Bolt11Invoice {
+    /// Amount in millisats
+    pub amount_msat: Option<u64>,
+    pub payment_hash: Option<[u8; 32]>,
+    pub description_hash: Option<[u8; 32]>,
+}
+
+impl Bolt11Invoice {
+    /// Decode invoice
+    ///
+    /// Return `None` if the invoice is malformed or the checksum doesn't match.
+    pub fn decode(invoice: &str) -> Option<Self> {
+        let invoice: String = invoice.trim().to_lowercase();
+        let invoice: &str = invoice.strip_prefix("lightning:").unwrap_or(&invoice);
+
+        let separator: usize = invoice.rfind('1')?;
+        let (hrp, data) = invoice.split_at(separator);
+        let data: Vec<u8> = data[1..]
+            .chars()
+            .map(|c| CHARSET.find(c).map(|i| i as u8))
+            .collect::<Option<_>>()?;
+
+        if !hrp.starts_with("ln") || data.len() < TIMESTAMP_LEN + SIGNATURE_LEN + CHECKSUM_LEN {
+            return None;
+        }
+
+        if !verify_checksum(hrp, &data) {
+            return None;
+        }
+
+        let mut invoice = Self {
+            amount_msat: parse_amount(hrp)?,
+            payment_hash: None,
+            description_hash: None,
+        };
+
+        let fields: &[u8] = &data[TIMESTAMP_LEN..data.len() - SIGNATURE_LEN - CHECKSUM_LEN];
+        let mut index: usize = 0;
+
+        while index + 3 <= fields.len() {
+            let field: u8 = fields[index];
+            let len: usize = (fields[index + 1] as usize) * 32 + fields[index + 2] as usize;
+            let start: usize = index + 3;
+            let end: usize = start + len;
+
+            if end > fields.len() {
+                return None;
+            }
+
+            let value: &[u8] = &fields[start..end];
+
+            match field {
+                PAYMENT_HASH_FIELD => invoice.payment_hash = to_hash(value),
+                DESCRIPTION_HASH_FIELD => invoice.description_hash = to_hash(value),
+                _ => {}
+            }
+
+            index = end;
+        }
+
+        Some(invoice)
+    }
+}
+
+/// Parse the amount from the human-readable part (i.e. `lnbc2500u`)
+///
+/// Returns `Some(None)` for amountless invoices and `None` if the amount is malformed.
+fn parse_amount(hrp: &str) -> Option<Option<u64>> {
+    let rest: &str = hrp.strip_prefix("ln")?;
+
+    // Amountless invoice
+    let Some(start) = rest.find(|c: char| c.is_ascii_digit()) else {
+        return Some(None);
+    };
+
+    let amount: &str = &rest[start..];
+
+    let (digits, multiplier) = match amount.chars().last()? {
+        c if c.is_ascii_digit() => (amount, None),
+        c => (&amount[..amount.len() - 1], Some(c)),
+    };
+
+    if digits.is_empty() || !digits.chars().all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+
+    let value: u64 = digits.parse().ok()?;
+
+    let msat: u64 = match multiplier {
+        None => value.checked_mul(100_000_000_000)?,
+        Some('m') => value.checked_mul(100_000_000)?,
+        Some('u') => value.checked_mul(100_000)?,
+        Some('n') => value.checked_mul(100)?,
+        Some('p') if value % 10 == 0 => value / 10,
+        _ => return None,
+    };
+
+    Some(Some(msat))
+}
+
+fn to_hash(value: &[u8]) -> Option<[u8; 32]> {
+    // 32 bytes are encoded in 52 groups of 5 bits
+    if value.len() != 52 {
+        return None;
+    }
+
+    let bytes: Vec<u8> = convert_bits(value);
+    bytes.get(..32)?.try_into().ok()
+}
+
+/// Convert 5-bit groups to bytes, discarding the padding
+fn convert_bits(data: &[u8]) -> Vec<u8> {
+    let mut acc: u32 = 0;
+    let mut bits: u32 = 0;
+    let mut output: Vec<u8> = Vec::with_capacity(data.len() * 5 / 8);
+
+    for value in data.iter() {
+        acc = (acc << 5) | *value as u32;
+        bits += 5;
+
+        while bits >= 8 {
+            bits -= 8;
+            output.push((acc >> bits) as u8);
+        }
+
+        acc &= (1 << bits) - 1;
+    }
+
+    output
+}
+
+fn verify_checksum(hrp: &str, data: &[u8]) -> bool {
+    let mut values: Vec<u8> = Vec::with_capacity(hrp.len() * 2 + 1 + data.len());
+    values.extend(hrp.bytes().map(|b| b >> 5));
+    values.push(0);
+    values.extend(hrp.bytes().map(|b| b & 31));
+    values.extend_from_slice(data);
+    polymod(&values) == 1
+}
+
+fn polymod(values: &[u8]) -> u32 {
+    let mut checksum: u32 = 1;
+
+    for value in values.iter() {
+        let top: u32 = checksum >> 25;
+        checksum = ((checksum & 0x1ffffff) << 5) ^ *value as u32;
+
+        for (i, generator) in GENERATOR.iter().enumerate() {
+            if (top >> i) & 1 == 1 {
+                checksum ^= generator;
+            }
+        }
+    }
+
+    checksum
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PAYMENT_HASH: [u8; 32] = [
+        0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x00, 0x01, 0x02, 0x03, 0x04,
+        0x05, 0x06, 0x07, 0x08, 0x09, 0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09,
+        0x01, 0x02,
+    ];
+
+    // BOLT11 spec examples
+    const DONATION: &str = "lnbc1pvjluezpp5qqqsyqcyq5rqwzqfqqqsyqcyq5rqwzqfqqqsyqcyq5rqwzqfqypqdpl2pkx2ctnv5sxxmmwwd5kgetjypeh2ursdae8g6twvus8g6rfwvs8qun0dfjkxaq8rkx3yf5tcsyz3d73gafnh3cax9rn449d9p5uxz9ezhhypd0elx87sjle52x86fux2ypatgddc6k63n7erqz25le42c4u4ecky03ylcqca784w";
+    const COFFEE: &str = "lnbc2500u1pvjluezpp5qqqsyqcyq5rqwzqfqqqsyqcyq5rqwzqfqqqsyqcyq5rqwzqfqypqdq5xysxxatsyp3k7enxv4jsxqzpuaztrnwngzn3kdzw5hydlzf03qdgm2hdq27cqv3agm2awhz5se903vruatfhq77w3ls4evs3ch9zw97j25emudupq63nyw24cg27h2rspfj9srp";
+    const CAKE: &str = "lnbc20m1pvjluezpp5qqqsyqcyq5rqwzqfqqqsyqcyq5rqwzqfqqqsyqcyq5rqwzqfqypqhp58yjmdan79s6qqdhdzgynm4zwqd5d7xmw5fk98klysy043l2ahrqscc6gd6ql3jrc5yzme8v4ntcewwz5cnw92tz0pc8qcuufvq7khhr8wpald05e92xw006sq94mg8v2ndf4sefvf9sygkshp5zfem29trqq2yxxz7";
+
+    #[test]
+    fn test_decode_amountless() {
+        let invoice = Bolt11Invoice::decode(DONATION).unwrap();
+        assert_eq!(invoice.amount_msat, None);
+        assert_eq!(invoice.payment_hash, Some(PAYMENT_HASH));
+        assert_eq!(invoice.description_hash, None);
+    }
+
+    #[test]
+    fn test_decode_amount() {
+        let invoice = Bolt11Invoice::decode(COFFEE).unwrap();
+        assert_eq!(invoice.amount_msat, Some(250_000_000));
+        assert_eq!(invoice.payment_hash, Some(PAYMENT_HASH));
+    }
+
+    #[test]
+    fn test_decode_description_hash() {
+        let invoice = Bolt11Invoice::decode(CAKE).unwrap();
+        assert_eq!(invoice.amount_msat, Some(2_000_000_000));
+        assert_eq!(invoice.payment_hash, Some(PAYMENT_HASH));
+
+        let description_hash: [u8; 32] = [
+            0x39, 0x25, 0xb6, 0xf6, 0x7e, 0x2c, 0x34, 0x00, 0x36, 0xed, 0x12, 0x09, 0x3d, 0xd4,
+            0x4e, 0x03, 0x68, 0xdf, 0x1b, 0x6e, 0xa2, 0x6c, 0x53, 0xdb, 0xe4, 0x81, 0x1f, 0x58,
+            0xfd, 0x5d, 0xb8, 0xc1,
+        ];
+        assert_eq!(invoice.description_hash, Some(description_hash));
+    }
+
+    #[test]
+    fn test_decode_uppercase_and_uri() {
+        let invoice = Bolt11Invoice::decode(&COFFEE.to_uppercase()).unwrap();
+        assert_eq!(invoice.amount_msat, Some(250_000_000));
+
+        let invoice = Bolt11Invoice::decode(&format!("lightning:{COFFEE}")).unwrap();
+        assert_eq!(invoice.amount_msat, Some(250_000_000));
+    }
+
+    #[test]
+    fn test_bad_checksum() {
+        let mut invoice: String = COFFEE.to_string();
+        let last: char = invoice.pop().unwrap();
+        invoice.push(if last == 'q' { 'p' } else { 'q' });
+        assert!(Bolt11Invoice::decode(&invoice).is_none());
+
+        // Tampered amount
+        let invoice: String = COFFEE.replacen("2500u", "2u", 1);
+        assert!(Bolt11Invoice::decode(&invoice).is_none());
+    }
+
+    #[test]
+    fn test_malformed() {
+        assert!(Bolt11Invoice::decode("").is_none());
+        assert!(Bolt11Invoice::decode("lnbc1").is_none());
+        assert!(Bolt11Invoice::decode("lnbc2500u1pvjlue").is_none());
+        assert!(Bolt11Invoice::decode(&COFFEE.replacen('q', "b", 1)).is_none());
+    }
+
+    #[test]
+    fn test_parse_amount_multipliers() {
+        assert_eq!(parse_amount("lnbc"), Some(None));
+        assert_eq!(parse_amount("lntb"), Some(None));
+        assert_eq!(parse_amount("lnbc1"), Some(Some(100_000_000_000)));
+        assert_eq!(parse_amount("lnbc1m"), Some(Some(100_000_000)));
+        assert_eq!(parse_amount("lnbc2500u"), Some(Some(250_000_000)));
+        assert_eq!(parse_amount("lnbc1n"), Some(Some(100)));
+        assert_eq!(parse_amount("lnbc10p"), Some(Some(1)));
+        assert_eq!(parse_amount("lnbcrt25m"), Some(Some(2_500_000_000)));
+    }
+
+    #[test]
+    fn test_parse_amount_invalid() {
+        // Sub-millisatoshi amount
+        assert_eq!(parse_amount("lnbc1p"), None);
+        // Unknown multiplier
+        assert_eq!(parse_amount("lnbc25x"), None);
+        assert_eq!(parse_amount("lnbc2x5u"), None);
+        // Overflow
+        assert_eq!(parse_amount("lnbc184467440737"), None);
+        assert_eq!(parse_amount("bc2500u"), None);
+    }
+}
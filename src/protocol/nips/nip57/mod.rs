@@ -0,0 +1,312 @@
+// Copyright (c) 2022-2023 Yuki Kishimoto
+// Copyright (c) 2023-2025 Rust Nostr Developers
+// Distributed under the MIT software license
+
+use std::ops::Deref;
+use std::str::FromStr;
+use std::sync::Arc;
+
+use nostr::JsonUtil;
+use nostr::hashes::Hash;
+use nostr::hashes::sha256::Hash as Sha256Hash;
+use nostr::nips::{nip01, nip57};
+use uniffi::{Object, Record};
+
+mod bolt11;
+
+pub(crate) use self::bolt11::Bolt11Invoice;
+use crate::error::{NostrSdkError, Result};
+use crate::protocol::event::{Event, EventId};
+use crate::protocol::key::PublicKey;
+#[cfg(feature = "nip57")]
+use crate::protocol::key::{Keys, SecretKey};
+use crate::protocol::nips::nip01::Coordinate;
+use crate::protocol::types::RelayUrl;
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Object)]
+#[uniffi::export(Debug, Eq, Hash)]
+pub struct ZapRequestData {
+    inner: nip57::ZapRequestData,
+}
+
+impl Deref for ZapRequestData {
+    type Target = nip57::ZapRequestData;
+
+    fn deref(&self) -> &Self::Target {
+        &self.inner
+    }
+}
+
+impl From<nip57::ZapRequestData> for ZapRequestData {
+    fn from(inner: nip57::ZapRequestData) -> Self {
+        Self { inner }
+    }
+}
+
+#[uniffi::export]
+impl ZapRequestData {
+    #[uniffi::constructor]
+    pub fn new(public_key: &PublicKey, relays: Vec<Arc<RelayUrl>>) -> Self {
+        Self {
+            inner: nip57::ZapRequestData::new(
+                **public_key,
+                relays.into_iter().map(|u| u.as_ref().deref().clone()),
+            ),
+        }
+    }
+
+    pub fn message(&self, message: &str) -> Self {
+        let mut builder = self.clone();
+        builder.inner = builder.inner.message(message);
+        builder
+    }
+
+    pub fn amount(&self, amount: u64) -> Self {
+        let mut builder = self.clone();
+        builder.inner = builder.inner.amount(amount);
+        builder
+    }
+
+    pub fn lnurl(&self, lnurl: &str) -> Self {
+        let mut builder = self.clone();
+        builder.inner = builder.inner.lnurl(lnurl);
+        builder
+    }
+
+    pub fn event_id(&self, event_id: &EventId) -> Self {
+        let mut builder = self.clone();
+        builder.inner = builder.inner.event_id(**event_id);
+        builder
+    }
+}
+
+/// Anonymous zap request
+///
+/// The zap request is signed with random keys and contains an empty `anon` tag,
+/// so the sender isn't revealed: for this reason the signed event is returned,
+/// instead of an [`EventBuilder`](crate::protocol::event::EventBuilder).
+///
+/// <https://github.com/nostr-protocol/nips/blob/master/57.md>
+#[cfg(feature = "nip57")]
+#[uniffi::export]
+pub fn nip57_anonymous_zap_request(data: &ZapRequestData) -> Result<Event> {
+    Ok(nip57::anonymous_zap_request(data.deref().clone())?.into())
+}
+
+/// Private zap request
+///
+/// The message is encrypted for the zap recipient and the zap request is signed with a key
+/// derived from the sender keys: for this reason the signed event is returned,
+/// instead of an [`EventBuilder`](crate::protocol::event::EventBuilder).
+///
+/// <https://github.com/nostr-protocol/nips/blob/master/57.md>
+#[cfg(feature = "nip57")]
+#[uniffi::export]
+pub fn nip57_private_zap_request(data: &ZapRequestData, keys: &Keys) -> Result<Event> {
+    Ok(nip57::private_zap_request(data.deref().clone(), keys.deref())?.into())
+}
+
+/// Decrypt a private zap message as sender
+///
+/// `public_key` is the public key of the zap recipient.
+///
+/// <https://github.com/nostr-protocol/nips/blob/master/57.md>
+#[cfg(feature = "nip57")]
+#[uniffi::export]
+pub fn nip57_decrypt_sent_private_zap_message(
+    secret_key: &SecretKey,
+    public_key: &PublicKey,
+    private_zap: &Event,
+) -> Result<Event> {
+    Ok(nip57::decrypt_sent_private_zap_message(
+        secret_key.deref(),
+        public_key.deref(),
+        private_zap.deref(),
+    )?
+    .into())
+}
+
+/// Decrypt a private zap message as recipient
+///
+/// <https://github.com/nostr-protocol/nips/blob/master/57.md>
+#[cfg(feature = "nip57")]
+#[uniffi::export]
+pub fn nip57_decrypt_received_private_zap_message(
+    secret_key: &SecretKey,
+    private_zap: &Event,
+) -> Result<Event> {
+    Ok(
+        nip57::decrypt_received_private_zap_message(secret_key.deref(), private_zap.deref())?
+            .into(),
+    )
+}
+
+/// Zap receipt
+///
+/// <https://github.com/nostr-protocol/nips/blob/master/57.md>
+#[derive(Record)]
+pub struct ZapReceipt {
+    /// Zap recipient
+    pub recipient: Arc<PublicKey>,
+    /// Zap sender
+    ///
+    /// `None` if the zap is anonymous or private.
+    pub sender: Option<Arc<PublicKey>>,
+    /// Zapped event
+    pub event_id: Option<Arc<EventId>>,
+    /// Zapped addressable event
+    pub coordinate: Option<Arc<Coordinate>>,
+    /// Amount in millisats, as encoded in the `bolt11` invoice
+    pub amount: Option<u64>,
+    /// Bolt11 invoice
+    pub bolt11: String,
+    /// Payment preimage
+    pub preimage: Option<String>,
+    /// Zap message (content of the zap request)
+    pub message: String,
+    /// Zap request embedded in the `description` tag
+    pub zap_request: Arc<Event>,
+}
+
+/// Parse and validate a zap receipt (kind `9735`)
+///
+/// Checks that:
+/// * the receipt signature is valid;
+/// * the embedded zap request (`description` tag) is a valid kind `9734` event;
+/// * the `bolt11` description hash commits to the zap request;
+/// * the `bolt11` amount matches the `amount` of the zap request, if any;
+/// * the `p`, `e` and `a` tags match the ones of the zap request;
+/// * the receipt is signed by `provider`, if provided (the `nostrPubkey` of the LNURL server).
+///
+/// <https://github.com/nostr-protocol/nips/blob/master/57.md>
+#[uniffi::export(default(provider = None))]
+pub fn nip57_parse_zap_receipt(
+    receipt: &Event,
+    provider: Option<Arc<PublicKey>>,
+) -> Result<ZapReceipt> {
+    let receipt: &nostr::Event = receipt.deref();
+
+    if receipt.kind != nostr::Kind::ZapReceipt {
+        return Err(NostrSdkError::Event {
+            msg: String::from("Event is not a zap receipt"),
+        });
+    }
+
+    receipt.verify()?;
+
+    if let Some(provider) = provider {
+        if receipt.pubkey != **provider {
+            return Err(NostrSdkError::Event {
+                msg: String::from("Zap receipt not signed by the LNURL provider"),
+            });
+        }
+    }
+
+    let bolt11: String =
+        first_tag_value(receipt, "bolt11").ok_or_else(|| NostrSdkError::Event {
+            msg: String::from("Missing bolt11 tag"),
+        })?;
+    let description: String =
+        first_tag_value(receipt, "description").ok_or_else(|| NostrSdkError::Event {
+            msg: String::from("Missing description tag"),
+        })?;
+    let preimage: Option<String> = first_tag_value(receipt, "preimage");
+
+    // Validate zap request
+    let zap_request: nostr::Event = nostr::Event::from_json(&description)?;
+    zap_request.verify()?;
+
+    if zap_request.kind != nostr::Kind::ZapRequest {
+        return Err(NostrSdkError::Event {
+            msg: String::from("Description is not a zap request"),
+        });
+    }
+
+    // Validate invoice against the zap request
+    let invoice: Bolt11Invoice =
+        Bolt11Invoice::decode(&bolt11).ok_or_else(|| NostrSdkError::Event {
+            msg: String::from("Invalid bolt11 invoice"),
+        })?;
+
+    let description_hash: [u8; 32] = Sha256Hash::hash(description.as_bytes()).to_byte_array();
+    if invoice.description_hash != Some(description_hash) {
+        return Err(NostrSdkError::Event {
+            msg: String::from("Invoice description hash doesn't match the zap request"),
+        });
+    }
+
+    if let Some(requested) = first_tag_value(&zap_request, "amount") {
+        let requested: u64 = requested.parse().map_err(|_| NostrSdkError::Event {
+            msg: String::from("Invalid zap request amount"),
+        })?;
+
+        if invoice.amount_msat != Some(requested) {
+            return Err(NostrSdkError::Event {
+                msg: String::from("Invoice amount doesn't match the zap request"),
+            });
+        }
+    }
+
+    // Validate target
+    let recipient: String =
+        first_tag_value(&zap_request, "p").ok_or_else(|| NostrSdkError::Event {
+            msg: String::from("Zap request without recipient"),
+        })?;
+
+    if first_tag_value(receipt, "p").as_ref() != Some(&recipient) {
+        return Err(NostrSdkError::Event {
+            msg: String::from("Zap receipt recipient doesn't match the zap request"),
+        });
+    }
+
+    let event_id: Option<String> = first_tag_value(&zap_request, "e");
+    if event_id.is_some() && first_tag_value(receipt, "e") != event_id {
+        return Err(NostrSdkError::Event {
+            msg: String::from("Zap receipt event doesn't match the zap request"),
+        });
+    }
+
+    let coordinate: Option<String> = first_tag_value(&zap_request, "a");
+    if coordinate.is_some() && first_tag_value(receipt, "a") != coordinate {
+        return Err(NostrSdkError::Event {
+            msg: String::from("Zap receipt coordinate doesn't match the zap request"),
+        });
+    }
+
+    let anonymous: bool = zap_request
+        .tags
+        .iter()
+        .any(|t| t.as_slice().first().map(|k| k.as_str()) == Some("anon"));
+
+    Ok(ZapReceipt {
+        recipient: Arc::new(nostr::PublicKey::from_hex(&recipient)?.into()),
+        sender: if anonymous {
+            None
+        } else {
+            Some(Arc::new(zap_request.pubkey.into()))
+        },
+        event_id: match event_id {
+            Some(id) => Some(Arc::new(nostr::EventId::from_hex(&id)?.into())),
+            None => None,
+        },
+        coordinate: match coordinate {
+            Some(coordinate) => Some(Arc::new(nip01::Coordinate::from_str(&coordinate)?.into())),
+            None => None,
+        },
+        amount: invoice.amount_msat,
+        bolt11,
+        preimage,
+        message: zap_request.content.clone(),
+        zap_request: Arc::new(zap_request.into()),
+    })
+}
+
+fn first_tag_value(event: &nostr::Event, kind: &str) -> Option<String> {
+    event.tags.iter().find_map(|t| {
+        let tag: &[String] = t.as_slice();
+        match tag.first() {
+            Some(k) if k == kind => tag.get(1).cloned(),
+            _ => None,
+        }
+    })
+}
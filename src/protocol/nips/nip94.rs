@@ -0,0 +1,365 @@
+// Copyright (c) 2022-2023 Yuki Kishimoto
+// Copyright (c) 2023-2025 Rust Nostr Developers
+// Distributed under the MIT software license
+
+use std::str::FromStr;
+
+use nostr::hashes::sha256::Hash as Sha256Hash;
+use uniffi::Object;
+
+use crate::error::{NostrSdkError, Result};
+use crate::protocol::event::{Event, Tag};
+use crate::protocol::types::ImageDimensions;
+
+/// File metadata
+///
+/// Used for both file metadata events (kind `1063`) and inline media metadata (`imeta` tags).
+///
+/// <https://github.com/nostr-protocol/nips/blob/master/94.md>
+/// <https://github.com/nostr-protocol/nips/blob/master/92.md>
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Object)]
+#[uniffi::export(Debug, Eq, Hash)]
+pub struct FileMetadata {
+    url: String,
+    mime_type: Option<String>,
+    sha256: Option<String>,
+    original_sha256: Option<String>,
+    size: Option<u64>,
+    dimensions: Option<nostr::ImageDimensions>,
+    magnet: Option<String>,
+    blurhash: Option<String>,
+    thumbnails: Vec<String>,
+    image: Option<String>,
+    summary: Option<String>,
+    alt: Option<String>,
+    fallbacks: Vec<String>,
+}
+
+impl FileMetadata {
+    fn with_url(url: String) -> Self {
+        Self {
+            url,
+            mime_type: None,
+            sha256: None,
+            original_sha256: None,
+            size: None,
+            dimensions: None,
+            magnet: None,
+            blurhash: None,
+            thumbnails: Vec::new(),
+            image: None,
+            summary: None,
+            alt: None,
+            fallbacks: Vec::new(),
+        }
+    }
+
+    /// Set a field from a key-value pair (tag kind and first value)
+    fn set(&mut self, key: &str, value: &str) {
+        match key {
+            "url" => self.url = value.to_string(),
+            "m" => self.mime_type = Some(value.to_string()),
+            "x" => self.sha256 = Some(value.to_string()),
+            "ox" => self.original_sha256 = Some(value.to_string()),
+            "size" => self.size = value.parse().ok(),
+            "dim" => {
+                self.dimensions = value.split_once('x').and_then(|(width, height)| {
+                    Some(nostr::ImageDimensions {
+                        width: width.parse().ok()?,
+                        height: height.parse().ok()?,
+                    })
+                })
+            }
+            "magnet" => self.magnet = Some(value.to_string()),
+            "blurhash" => self.blurhash = Some(value.to_string()),
+            "thumb" => self.thumbnails.push(value.to_string()),
+            "image" => self.image = Some(value.to_string()),
+            "summary" => self.summary = Some(value.to_string()),
+            "alt" => self.alt = Some(value.to_string()),
+            "fallback" => self.fallbacks.push(value.to_string()),
+            _ => {}
+        }
+    }
+
+    /// Get the key-value pairs, in the order defined by NIP-94
+    fn pairs(&self) -> Vec<(&'static str, String)> {
+        let mut pairs: Vec<(&'static str, String)> = vec![("url", self.url.clone())];
+
+        if let Some(mime_type) = &self.mime_type {
+            pairs.push(("m", mime_type.clone()));
+        }
+
+        if let Some(sha256) = &self.sha256 {
+            pairs.push(("x", sha256.clone()));
+        }
+
+        if let Some(original_sha256) = &self.original_sha256 {
+            pairs.push(("ox", original_sha256.clone()));
+        }
+
+        if let Some(size) = self.size {
+            pairs.push(("size", size.to_string()));
+        }
+
+        if let Some(dim) = &self.dimensions {
+            pairs.push(("dim", format!("{}x{}", dim.width, dim.height)));
+        }
+
+        if let Some(magnet) = &self.magnet {
+            pairs.push(("magnet", magnet.clone()));
+        }
+
+        if let Some(blurhash) = &self.blurhash {
+            pairs.push(("blurhash", blurhash.clone()));
+        }
+
+        for thumb in self.thumbnails.iter() {
+            pairs.push(("thumb", thumb.clone()));
+        }
+
+        if let Some(image) = &self.image {
+            pairs.push(("image", image.clone()));
+        }
+
+        if let Some(summary) = &self.summary {
+            pairs.push(("summary", summary.clone()));
+        }
+
+        if let Some(alt) = &self.alt {
+            pairs.push(("alt", alt.clone()));
+        }
+
+        for fallback in self.fallbacks.iter() {
+            pairs.push(("fallback", fallback.clone()));
+        }
+
+        pairs
+    }
+
+    /// Parse the values of an `imeta` tag (without the tag kind)
+    pub(crate) fn from_imeta_values(values: &[String]) -> Option<Self> {
+        let mut metadata: Self = Self::with_url(String::new());
+
+        for value in values.iter() {
+            if let Some((key, value)) = value.split_once(' ') {
+                metadata.set(key, value);
+            }
+        }
+
+        if metadata.url.is_empty() {
+            return None;
+        }
+
+        Some(metadata)
+    }
+
+    /// Compose the tags of a file metadata event
+    pub(crate) fn to_tags(&self) -> Vec<nostr::Tag> {
+        self.pairs()
+            .into_iter()
+            .map(|(key, value)| nostr::Tag::custom(key, [value].as_slice()))
+            .collect()
+    }
+
+    /// Compose the values of an `imeta` tag (without the tag kind)
+    pub(crate) fn to_imeta_values(&self) -> Vec<String> {
+        self.pairs()
+            .into_iter()
+            .map(|(key, value)| format!("{key} {value}"))
+            .collect()
+    }
+}
+
+#[uniffi::export]
+impl FileMetadata {
+    #[uniffi::constructor]
+    pub fn new(url: &str, mime_type: &str, sha256: &str) -> Result<Self> {
+        // Validate hash
+        Sha256Hash::from_str(sha256)?;
+
+        let mut metadata: Self = Self::with_url(url.to_string());
+        metadata.mime_type = Some(mime_type.to_string());
+        metadata.sha256 = Some(sha256.to_string());
+        Ok(metadata)
+    }
+
+    /// Parse file metadata event (kind `1063`)
+    #[uniffi::constructor]
+    pub fn from_event(event: &Event) -> Result<Self> {
+        if event.kind != nostr::Kind::FileMetadata {
+            return Err(NostrSdkError::Event {
+                msg: String::from("Event is not a file metadata"),
+            });
+        }
+
+        let mut metadata: Self = Self::with_url(String::new());
+
+        for tag in event.tags.iter() {
+            if let [key, value, ..] = tag.as_slice() {
+                metadata.set(key, value);
+            }
+        }
+
+        if metadata.url.is_empty() {
+            return Err(NostrSdkError::Event {
+                msg: String::from("Missing url tag"),
+            });
+        }
+
+        Ok(metadata)
+    }
+
+    /// Parse `imeta` tag
+    ///
+    /// <https://github.com/nostr-protocol/nips/blob/master/92.md>
+    #[uniffi::constructor]
+    pub fn from_imeta(tag: &Tag) -> Result<Self> {
+        match tag.as_slice().split_first() {
+            Some((kind, values)) if kind == "imeta" => {
+                Self::from_imeta_values(values).ok_or_else(|| NostrSdkError::Event {
+                    msg: String::from("Missing imeta url"),
+                })
+            }
+            _ => Err(NostrSdkError::Event {
+                msg: String::from("Not an imeta tag"),
+            }),
+        }
+    }
+
+    /// Set the SHA256 hash of the original file, before any server-side transformation
+    pub fn set_original_sha256(&self, hash: &str) -> Result<Self> {
+        Sha256Hash::from_str(hash)?;
+
+        let mut builder = self.clone();
+        builder.original_sha256 = Some(hash.to_string());
+        Ok(builder)
+    }
+
+    /// Set file size in bytes
+    pub fn set_size(&self, size: u64) -> Self {
+        let mut builder = self.clone();
+        builder.size = Some(size);
+        builder
+    }
+
+    /// Set file dimensions
+    pub fn set_dimensions(&self, dim: ImageDimensions) -> Self {
+        let mut builder = self.clone();
+        builder.dimensions = Some(dim.into());
+        builder
+    }
+
+    /// Set magnet URI
+    pub fn set_magnet(&self, magnet: &str) -> Self {
+        let mut builder = self.clone();
+        builder.magnet = Some(magnet.to_string());
+        builder
+    }
+
+    /// Set blurhash
+    pub fn set_blurhash(&self, blurhash: &str) -> Self {
+        let mut builder = self.clone();
+        builder.blurhash = Some(blurhash.to_string());
+        builder
+    }
+
+    /// Add thumbnail URL
+    pub fn add_thumbnail(&self, url: &str) -> Self {
+        let mut builder = self.clone();
+        builder.thumbnails.push(url.to_string());
+        builder
+    }
+
+    /// Set preview image URL
+    pub fn set_image(&self, url: &str) -> Self {
+        let mut builder = self.clone();
+        builder.image = Some(url.to_string());
+        builder
+    }
+
+    /// Set summary
+    pub fn set_summary(&self, summary: &str) -> Self {
+        let mut builder = self.clone();
+        builder.summary = Some(summary.to_string());
+        builder
+    }
+
+    /// Set alt text, for accessibility
+    pub fn set_alt(&self, alt: &str) -> Self {
+        let mut builder = self.clone();
+        builder.alt = Some(alt.to_string());
+        builder
+    }
+
+    /// Add fallback URL
+    pub fn add_fallback(&self, url: &str) -> Self {
+        let mut builder = self.clone();
+        builder.fallbacks.push(url.to_string());
+        builder
+    }
+
+    /// Get file URL
+    pub fn url(&self) -> String {
+        self.url.clone()
+    }
+
+    /// Get MIME type
+    pub fn mime_type(&self) -> Option<String> {
+        self.mime_type.clone()
+    }
+
+    /// Get SHA256 hash
+    pub fn sha256(&self) -> Option<String> {
+        self.sha256.clone()
+    }
+
+    /// Get SHA256 hash of the original file
+    pub fn original_sha256(&self) -> Option<String> {
+        self.original_sha256.clone()
+    }
+
+    /// Get file size in bytes
+    pub fn size(&self) -> Option<u64> {
+        self.size
+    }
+
+    /// Get file dimensions
+    pub fn dimensions(&self) -> Option<ImageDimensions> {
+        self.dimensions.map(|d| d.into())
+    }
+
+    /// Get magnet URI
+    pub fn magnet(&self) -> Option<String> {
+        self.magnet.clone()
+    }
+
+    /// Get blurhash
+    pub fn blurhash(&self) -> Option<String> {
+        self.blurhash.clone()
+    }
+
+    /// Get thumbnail URLs
+    pub fn thumbnails(&self) -> Vec<String> {
+        self.thumbnails.clone()
+    }
+
+    /// Get preview image URL
+    pub fn image(&self) -> Option<String> {
+        self.image.clone()
+    }
+
+    /// Get summary
+    pub fn summary(&self) -> Option<String> {
+        self.summary.clone()
+    }
+
+    /// Get alt text
+    pub fn alt(&self) -> Option<String> {
+        self.alt.clone()
+    }
+
+    /// Get fallback URLs
+    pub fn fallbacks(&self) -> Vec<String> {
+        self.fallbacks.clone()
+    }
+}
@@ -3,6 +3,6 @@ import XCTest
 
 final class NostrSDKTests: XCTestCase {
     func testExample() throws {
-        var client: Client = ClientBuilder.init().build()
+        var client: Client = try ClientBuilder.init().build()
     }
 }